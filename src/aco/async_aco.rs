@@ -1,78 +1,63 @@
-use rand::{rng, rngs::ThreadRng};
-
-use crate::{aco::{config::ACOConfig, logger::AsyncACOLogger}, conformation::Conformation, pheromones::{self, Pheromones}, protein::Protein};
+use crate::{aco::{config::ACOConfig, engine::{AcoEngine, AntStep}, logger::{AsyncACOLogger, Event}, report::RunReport, WarmStart}, protein::Protein};
 
 pub async fn async_aco_protein_folding_2dhp(
     protein: &Protein,
     config: ACOConfig,
     logger: impl AsyncACOLogger
-) -> (Conformation, f64)
+) -> RunReport
 {
-    let mut pheromones = Pheromones::new(&protein, config);
-
-    let mut best_conformation = Conformation::new(protein, config);
-    let mut best = f64::NEG_INFINITY;
-
-    for iteration in 0..config.max_iter {        
-        let mut conformations_zip = Vec::new();
-        
-        for _ in 0..config.ant_count {
-            let mut conf = Conformation::new(protein, config); /* Cria nova conformação */
-            let mut rng = rng();
-
-            while !conf.is_fully_grown() {
-                if !conf.grow(&pheromones, &mut rng) {
-                    conf.rewind();
-                }
-                logger.log_change(config, iteration, &conf, best, &pheromones).await;
-            }
-            
-            // Tenta melhorar solução encontrada
-            local_search_loop(&mut conf, config.no_impr_max, &mut rng, logger, config, iteration, best, &pheromones).await;
+    async_aco_protein_folding_2dhp_from(protein, config, logger, WarmStart::cold(protein, config)).await
+}
 
-            let fit = conf.eval(); /* Avalia para comparação */
+/// Como `async_aco_protein_folding_2dhp`, continuando a partir de `start`.
+pub async fn async_aco_protein_folding_2dhp_from(
+    protein: &Protein,
+    config: ACOConfig,
+    logger: impl AsyncACOLogger,
+    start: WarmStart
+) -> RunReport
+{
+    AcoEngine::with_start(protein, config, start).run_async(logger).await
+}
 
-            logger.log_ant(config, iteration, &conf, best, &pheromones).await;
+impl AcoEngine {
+    /// Como `run`, com as formigas uma de cada vez, passo a passo, para desenhar cada mudança.
+    /// O resultado é o mesmo.
+    pub async fn run_async(mut self, mut logger: impl AsyncACOLogger) -> RunReport {
+        logger.log_async(&self.start_event()).await;
 
-            conformations_zip.push((conf, fit));
-        };
+        'search: while !self.is_finished() {
+            let iteration = self.state().iteration();
+            let mut ants = self.start_iteration();
 
-        // Atualiza melhor solução
-        let conformations: Vec<_> = conformations_zip.into_iter()
-            .map(|(conf, fit)| {
-                if fit > best {
-                    best = fit;
-                    best_conformation = conf.clone();
+            for ant in &mut ants {
+                if self.check_cancelled() {
+                    break 'search;
                 }
-                conf
-            })
-            .collect();
 
-        pheromones.update(&conformations);
+                loop {
+                    let step = self.advance(ant);
+                    if step == AntStep::Finished {
+                        break;
+                    }
 
-        logger.log_iteration(config, iteration, &best_conformation, best, &pheromones).await;
-    }
+                    if let Some(event) = step.event(iteration, ant.index()) {
+                        logger.log_async(&event).await;
+                    }
+                    logger.log_async(&Event::Step { iteration, ant: ant.index(), conformation: ant.conformation() }).await;
+                }
 
-    (best_conformation, best)
-}
+                logger.log_async(&ant.finished_event(iteration)).await;
+            }
 
-async fn local_search_loop(
-    conformation: &mut Conformation,
-    no_impr_max: u16,
-    rng: &mut ThreadRng,
-    logger: impl AsyncACOLogger,
-    config: ACOConfig,
-    iter: u16,
-    best: f64,
-    pheromones: &Pheromones,
-) {
-    let mut no_impr = 0;
-    while no_impr < no_impr_max {
-        if conformation.local_search(rng) {
-            no_impr = 0;
-        } else {
-            no_impr += 1;
+            self.finish_iteration(ants);
+            for event in self.iteration_events() {
+                logger.log_async(&event).await;
+            }
         }
-        logger.log_change(config, iter, conformation, best, pheromones).await;
+
+        let report = self.into_report();
+        logger.log_async(&Event::RunEnd { report: &report }).await;
+        report
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,237 @@
+use std::{error::Error, fmt, fs, io, path::{Path, PathBuf}, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{aco::{config::ACOConfig, engine::{AcoEngine, RunState}, logger::{ACOLogger, Event}, report::{IterationStats, RunReport}, stop::StopReason}, pheromones::{PheromoneSnapshot, SnapshotError}, protein::{parse_protein_sequence_with, protein_to_string, EnergyModel, ParseError, Protein}};
+
+/* Incrementar sempre que um campo de `Checkpoint` mudar */
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+/// Estado completo de uma execução ao fim de uma iteração. Continuar dele com `resume` dá o
+/// mesmo resultado da execução sem interrupção: as formigas sorteiam com `ant_rng`, que só
+/// depende da seed e da iteração.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub format_version: u32,
+    /* Iterações entre um checkpoint e outro, mantido ao continuar */
+    pub every: u16,
+    /* Feromônios, proteína, configuração e melhor conformação */
+    pub pheromones: PheromoneSnapshot,
+    pub best_iteration: Option<u16>,
+    /* Uma entrada por iteração concluída */
+    pub iterations: Vec<IterationStats>,
+    pub evaluations: u64,
+    pub elapsed_secs: f64
+}
+
+impl Checkpoint {
+    fn new(protein: &Protein, state: &RunState, elapsed: Duration, every: u16) -> Self {
+        Self {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            every,
+            pheromones: state.pheromones.snapshot(protein, Some(&state.best_conformation)),
+            best_iteration: state.best_iteration,
+            iterations: state.iterations.clone(),
+            evaluations: state.evaluations,
+            elapsed_secs: elapsed.as_secs_f64()
+        }
+    }
+
+    pub fn config(&self) -> ACOConfig {
+        self.pheromones.config
+    }
+
+    /// Sequência da proteína, para montá-la com o modelo de energia da execução.
+    pub fn sequence(&self) -> &str {
+        &self.pheromones.sequence
+    }
+
+    /// Proteína do checkpoint, com o modelo de energia embutido gravado nele. Um modelo lido de
+    /// uma matriz não pode ser refeito só pelo nome: use `protein_with`.
+    pub fn protein(&self) -> Result<Protein, CheckpointError> {
+        let energy_model = &self.pheromones.energy_model;
+        let model = EnergyModel::builtin(energy_model).ok_or_else(|| CheckpointError::CustomModel(energy_model.clone()))?;
+
+        self.protein_with(&model)
+    }
+
+    /// Como `protein`, com o modelo `model`, que precisa ter o nome gravado no checkpoint.
+    pub fn protein_with(&self, model: &Arc<EnergyModel>) -> Result<Protein, CheckpointError> {
+        if model.name() != self.pheromones.energy_model {
+            return Err(CheckpointError::Protein { sequence: self.pheromones.sequence.clone(), energy_model: model.name().to_string() });
+        }
+
+        parse_protein_sequence_with(&self.pheromones.sequence, model).map_err(CheckpointError::Sequence)
+    }
+
+    /// Próxima iteração a executar.
+    pub fn iteration(&self) -> u16 {
+        self.iterations.len() as u16
+    }
+
+    /* Grava num arquivo temporário e renomeia, para uma interrupção no meio não estragar o anterior */
+    pub fn save(&self, path: &Path) -> Result<(), CheckpointError> {
+        let json = serde_json::to_string(self).map_err(|e| CheckpointError::Json(path.to_path_buf(), e))?;
+        let tmp = path.with_extension("tmp");
+
+        fs::write(&tmp, json)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| CheckpointError::Io(path.to_path_buf(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        let json = fs::read_to_string(path).map_err(|e| CheckpointError::Io(path.to_path_buf(), e))?;
+        let checkpoint: Self = serde_json::from_str(&json).map_err(|e| CheckpointError::Json(path.to_path_buf(), e))?;
+
+        if checkpoint.format_version != CHECKPOINT_FORMAT_VERSION {
+            return Err(CheckpointError::Version { found: checkpoint.format_version, expected: CHECKPOINT_FORMAT_VERSION });
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Continua a execução com a configuração salva, gravando os próximos checkpoints em `path`.
+    /// `protein` precisa ter a sequência e o modelo de energia do checkpoint.
+    pub fn resume<L>(self, path: &Path, protein: &Protein, logger: L) -> Result<RunReport, CheckpointError>
+    where
+        L: ACOLogger,
+    {
+        let every = self.every;
+        run_checkpointed(self.engine(protein)?, logger, path, every)
+    }
+
+    /// Busca parada no ponto do checkpoint, para continuar com `run_checkpointed` ou passo a passo.
+    pub fn engine(self, protein: &Protein) -> Result<AcoEngine, CheckpointError> {
+        let snapshot = &self.pheromones;
+        let sequence = protein_to_string(protein.residues());
+        let energy_model = protein.model().name();
+
+        if snapshot.sequence != sequence || snapshot.energy_model != energy_model {
+            return Err(CheckpointError::Protein { sequence, energy_model: energy_model.to_string() });
+        }
+
+        let config = self.config();
+        let mut state = RunState::new(protein, config, snapshot.warm_start(protein, config)?);
+        state.best_iteration = self.best_iteration;
+        state.iterations = self.iterations;
+        state.evaluations = self.evaluations;
+        state.elapsed = Duration::from_secs_f64(self.elapsed_secs);
+
+        Ok(AcoEngine::from_state(protein, config, state))
+    }
+}
+
+/// Como `aco_protein_folding_2dhp`, gravando o estado em `path` a cada `every` iterações para
+/// continuar depois com `resume_from`.
+pub fn aco_protein_folding_2dhp_checkpointed<L>(
+    protein: &Protein,
+    config: ACOConfig,
+    logger: L,
+    path: &Path,
+    every: u16
+) -> Result<RunReport, CheckpointError>
+where
+    L: ACOLogger,
+{
+    run_checkpointed(AcoEngine::new(protein, config), logger, path, every)
+}
+
+/// Continua uma execução de onde o checkpoint em `path` parou, com a proteína e o modelo de
+/// energia gravados nele (veja `Checkpoint::resume`).
+pub fn resume_from<L>(path: &Path, logger: L) -> Result<RunReport, CheckpointError>
+where
+    L: ACOLogger,
+{
+    let checkpoint = Checkpoint::load(path)?;
+    let protein = checkpoint.protein()?;
+
+    checkpoint.resume(path, &protein, logger)
+}
+
+/// Como `resume_from`, para execuções com um modelo lido de uma matriz: o checkpoint guarda só
+/// o nome do modelo, então `model` precisa ser passado de novo.
+pub fn resume_from_with<L>(path: &Path, model: &Arc<EnergyModel>, logger: L) -> Result<RunReport, CheckpointError>
+where
+    L: ACOLogger,
+{
+    let checkpoint = Checkpoint::load(path)?;
+    let protein = checkpoint.protein_with(model)?;
+
+    checkpoint.resume(path, &protein, logger)
+}
+
+/// Executa `engine` até o fim como `AcoEngine::run`, gravando o estado em `path` a cada `every` iterações.
+pub fn run_checkpointed<L>(mut engine: AcoEngine, mut logger: L, path: &Path, every: u16) -> Result<RunReport, CheckpointError>
+where
+    L: ACOLogger,
+{
+    let every = every.max(1);
+
+    logger.log(&engine.start_event());
+    while engine.step(&mut logger).is_some() {
+        /* Uma execução que parou por um critério não tem o que continuar */
+        if engine.stop_reason().is_none() && engine.state().iteration().is_multiple_of(every) {
+            Checkpoint::new(engine.protein(), engine.state(), engine.elapsed(), every).save(path)?;
+        }
+    }
+
+    /* Cancelada, ela pode continuar depois da última iteração completa, se houve alguma */
+    if engine.stop_reason() == Some(StopReason::Cancelled) && engine.state().iteration() > 0 {
+        Checkpoint::new(engine.protein(), engine.state(), engine.elapsed(), every).save(path)?;
+    }
+
+    let report = engine.into_report();
+    logger.log(&Event::RunEnd { report: &report });
+    Ok(report)
+}
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(PathBuf, io::Error),
+    Json(PathBuf, serde_json::Error),
+    Version { found: u32, expected: u32 },
+    /* Proteína que não é a do checkpoint */
+    Protein { sequence: String, energy_model: String },
+    /* Modelo que não é embutido, sem a matriz para refazê-lo */
+    CustomModel(String),
+    Sequence(ParseError),
+    Snapshot(SnapshotError)
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(path, e) => write!(f, "erro ao acessar '{}': {}", path.display(), e),
+            CheckpointError::Json(path, e) => write!(f, "{}: JSON inválido: {}", path.display(), e),
+            CheckpointError::Version { found, expected } => {
+                write!(f, "versão {} do checkpoint não é suportada (esperada: {})", found, expected)
+            }
+            CheckpointError::Protein { sequence, energy_model } => {
+                write!(f, "o checkpoint não é da proteína {} no modelo '{}'", sequence, energy_model)
+            }
+            CheckpointError::CustomModel(name) => {
+                write!(f, "o checkpoint é do modelo '{}', que não é embutido; informe a matriz de interação", name)
+            }
+            CheckpointError::Sequence(e) => write!(f, "sequência inválida no checkpoint: {}", e),
+            CheckpointError::Snapshot(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckpointError::Io(_, e) => Some(e),
+            CheckpointError::Json(_, e) => Some(e),
+            CheckpointError::Sequence(e) => Some(e),
+            CheckpointError::Snapshot(e) => Some(e),
+            CheckpointError::Version { .. } | CheckpointError::Protein { .. } | CheckpointError::CustomModel(_) => None
+        }
+    }
+}
+
+impl From<SnapshotError> for CheckpointError {
+    fn from(e: SnapshotError) -> Self {
+        CheckpointError::Snapshot(e)
+    }
+}
@@ -1,4 +1,8 @@
-#[derive(Debug, PartialEq, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+use crate::{aco::stop::StopCriteria, conformation::{backtracking::DEFAULT_BACKTRACK_DEPTH, lattice::LatticeKind, local_search::Neighbourhood, Backtracking, Construction, Lookahead}, pheromones::{PheromoneUpdate, UpdateParams}, protein::Energy};
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct ACOConfig {
     pub ant_count: u16,
     pub max_iter: u16,
@@ -6,5 +10,32 @@ pub struct ACOConfig {
     pub evaporation: f64,
     pub alpha: f64,
     pub beta: f64,
-    pub neutral_mutation_rate: f64
-}
\ No newline at end of file
+    pub neutral_mutation_rate: f64,
+    pub seed: u64,
+    #[serde(default)]
+    pub stop: StopCriteria,
+    #[serde(default)]
+    pub neighbourhood: Neighbourhood,
+    #[serde(default)]
+    pub lattice: LatticeKind,
+    #[serde(default)]
+    pub pheromone_update: PheromoneUpdate,
+    #[serde(default)]
+    pub update_params: UpdateParams,
+    #[serde(default)]
+    pub construction: Construction,
+    #[serde(default)]
+    pub backtracking: Backtracking,
+    /* Só no retrocesso `LimitedDepth` */
+    #[serde(default = "default_backtrack_depth")]
+    pub backtrack_depth: u16,
+    #[serde(default)]
+    pub lookahead: Lookahead,
+    /* Energia ótima conhecida da proteína, usada para relatar o erro relativo dos resultados */
+    #[serde(default)]
+    pub optimum: Option<Energy>
+}
+
+fn default_backtrack_depth() -> u16 {
+    DEFAULT_BACKTRACK_DEPTH
+}
@@ -0,0 +1,562 @@
+use std::{error::Error, fmt, mem, time::{Duration, Instant}};
+
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+
+use crate::{aco::{ant_rng, config::ACOConfig, logger::{ACOLogger, Event}, report::{IterationStats, RunReport}, stop::{CancellationToken, Progress, SearchProgress, StopReason}, WarmStart}, conformation::{lattice::LatticeKind, Conformation, ConstructionStats, Direction}, pheromones::Pheromones, protein::{Energy, Protein}};
+
+/// Estado da busca entre uma iteração e outra, o que um checkpoint precisa guardar. As formigas
+/// sorteiam com `ant_rng`, então a seed e a quantidade de iterações já feitas bastam para o sorteio.
+#[derive(Debug)]
+pub struct RunState {
+    pub(crate) pheromones: Pheromones,
+    pub(crate) best_conformation: Conformation,
+    pub(crate) best: f64,
+    pub(crate) best_iteration: Option<u16>,
+    pub(crate) iterations: Vec<IterationStats>,
+    pub(crate) evaluations: u64,
+    /* Tempo gasto antes de continuar de um checkpoint */
+    pub(crate) elapsed: Duration
+}
+
+impl RunState {
+    pub(crate) fn new(protein: &Protein, config: ACOConfig, start: WarmStart) -> Self {
+        let WarmStart { pheromones, best } = start;
+
+        Self {
+            pheromones,
+            best: best.as_ref().map_or(f64::NEG_INFINITY, Conformation::eval),
+            best_conformation: best.unwrap_or_else(|| Conformation::new(protein, config)),
+            best_iteration: None,
+            iterations: Vec::with_capacity(config.max_iter as usize),
+            evaluations: 0,
+            elapsed: Duration::ZERO
+        }
+    }
+
+    /// Próxima iteração a executar.
+    pub fn iteration(&self) -> u16 {
+        self.iterations.len() as u16
+    }
+
+    pub fn pheromones(&self) -> &Pheromones {
+        &self.pheromones
+    }
+
+    /// Energia da melhor conformação até agora, na convenção de `Energy::from_fitness` se ainda não há nenhuma.
+    pub fn best_energy(&self) -> Energy {
+        Energy::from_fitness(self.best)
+    }
+
+    /* Iteração em que a melhor conformação foi encontrada */
+    pub fn best_iteration(&self) -> Option<u16> {
+        self.best_iteration
+    }
+
+    /// Estatísticas das iterações concluídas.
+    pub fn iterations(&self) -> &[IterationStats] {
+        &self.iterations
+    }
+
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations
+    }
+}
+
+/// Uma formiga da iteração atual: constrói a conformação e depois a melhora com a busca local,
+/// um passo por chamada de `advance`.
+#[derive(Debug, Clone)]
+pub struct Ant {
+    index: u16,
+    conformation: Conformation,
+    rng: ChaCha8Rng,
+    no_impr_max: u16,
+    /* Tentativas seguidas da busca local sem melhora */
+    no_impr: u16,
+    improvements: u32,
+    /* Contadores da construção, guardados ao completar a conformação */
+    construction: ConstructionStats,
+    fitness: Option<f64>,
+    /* Passos para os loggers guardados por `run`, na execução em paralelo */
+    events: Vec<AntStep>
+}
+
+impl Ant {
+    fn new(protein: &Protein, config: ACOConfig, iteration: u16, index: u16) -> Self {
+        Self {
+            index,
+            conformation: Conformation::new(protein, config),
+            rng: ant_rng(config.seed, iteration, index),
+            no_impr_max: config.no_impr_max,
+            no_impr: 0,
+            improvements: 0,
+            construction: ConstructionStats::default(),
+            fitness: None,
+            events: Vec::new()
+        }
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn conformation(&self) -> &Conformation {
+        &self.conformation
+    }
+
+    /// Energia da conformação final, depois que `advance` terminou.
+    pub fn energy(&self) -> Option<Energy> {
+        self.fitness.map(Energy::from_fitness)
+    }
+
+    /// Coloca (ou retrocede) um aminoácido enquanto a conformação não está completa; depois, faz
+    /// uma tentativa da busca local.
+    pub fn advance(&mut self, pheromones: &Pheromones) -> AntStep {
+        if self.fitness.is_some() {
+            return AntStep::Finished;
+        }
+
+        if !self.conformation.is_fully_grown() {
+            let step = if self.conformation.grow(pheromones, &mut self.rng) {
+                AntStep::Placed
+            } else {
+                let undone = self.construction.undone_steps;
+                self.conformation.rewind();
+                AntStep::Backtracked { undone: self.conformation.construction_stats().undone_steps - undone }
+            };
+            self.construction = self.conformation.construction_stats();
+            return step;
+        }
+
+        if self.no_impr >= self.no_impr_max {
+            self.fitness = Some(self.conformation.eval()); /* Avalia para comparação */
+            return AntStep::Finished;
+        }
+
+        // Tenta melhorar solução encontrada
+        if self.conformation.local_search(&mut self.rng) {
+            self.no_impr = 0;
+            self.improvements += 1;
+            AntStep::Improved { energy: self.conformation.energy() }
+        } else {
+            self.no_impr += 1;
+            AntStep::NotImproved
+        }
+    }
+
+    /// Evento da formiga que terminou, com a energia final.
+    pub fn finished_event(&self, iteration: u16) -> Event<'_> {
+        Event::Ant {
+            iteration,
+            ant: self.index,
+            conformation: &self.conformation,
+            energy: Energy::from_fitness(self.fitness.unwrap_or_else(|| self.conformation.eval()))
+        }
+    }
+
+    /* Executa até o fim, guardando os retrocessos e as melhoras para os loggers */
+    fn run(&mut self, pheromones: &Pheromones) {
+        loop {
+            match self.advance(pheromones) {
+                AntStep::Finished => break,
+                step @ (AntStep::Backtracked { .. } | AntStep::Improved { .. }) => self.events.push(step),
+                AntStep::Placed | AntStep::NotImproved => {}
+            }
+        }
+    }
+}
+
+/// O que um passo de `Ant::advance` fez.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AntStep {
+    Placed,
+    /* Ficou sem saída e desfez `undone` passos */
+    Backtracked { undone: u32 },
+    /* A busca local melhorou a conformação */
+    Improved { energy: Energy },
+    NotImproved,
+    /* A formiga já terminou; `Ant::energy` tem a energia final */
+    Finished
+}
+
+impl AntStep {
+    /// O evento correspondente para os loggers, se o passo tem um.
+    pub fn event<'a>(self, iteration: u16, ant: u16) -> Option<Event<'a>> {
+        match self {
+            AntStep::Backtracked { undone } => Some(Event::Backtrack { iteration, ant, undone }),
+            AntStep::Improved { energy } => Some(Event::Improvement { iteration, ant, energy }),
+            AntStep::Placed | AntStep::NotImproved | AntStep::Finished => None
+        }
+    }
+}
+
+/// O ACO passo a passo: cada `step` executa uma iteração inteira. Para acompanhar cada formiga,
+/// use `start_iteration`, `AcoEngine::advance` e `finish_iteration`, que dão o mesmo resultado.
+pub struct AcoEngine {
+    protein: Protein,
+    config: ACOConfig,
+    state: RunState,
+    /* Conformações de fora que entram na próxima iteração (veja `inject`) */
+    injected: Vec<Conformation>,
+    stop_reason: Option<StopReason>,
+    cancel: Option<CancellationToken>,
+    on_progress: Option<Box<dyn FnMut(Progress) + Send>>,
+    start: Instant,
+    iteration_start: Instant
+}
+
+impl fmt::Debug for AcoEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AcoEngine")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .field("stop_reason", &self.stop_reason)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AcoEngine {
+    pub fn new(protein: &Protein, config: ACOConfig) -> Self {
+        Self::with_start(protein, config, WarmStart::cold(protein, config))
+    }
+
+    /// Começa a partir de `start` (veja `PheromoneSnapshot`).
+    pub fn with_start(protein: &Protein, config: ACOConfig, start: WarmStart) -> Self {
+        Self::from_state(protein, config, RunState::new(protein, config, start))
+    }
+
+    pub(crate) fn from_state(protein: &Protein, config: ACOConfig, state: RunState) -> Self {
+        let now = Instant::now();
+
+        Self {
+            protein: protein.clone(),
+            config,
+            state,
+            injected: Vec::new(),
+            stop_reason: None,
+            cancel: None,
+            on_progress: None,
+            start: now,
+            iteration_start: now
+        }
+    }
+
+    pub fn protein(&self) -> &Protein {
+        &self.protein
+    }
+
+    pub fn config(&self) -> ACOConfig {
+        self.config
+    }
+
+    pub fn state(&self) -> &RunState {
+        &self.state
+    }
+
+    /// Melhor conformação até agora e sua energia, se alguma iteração já terminou.
+    pub fn best(&self) -> Option<(&Conformation, Energy)> {
+        self.state.best.is_finite().then(|| (&self.state.best_conformation, self.state.best_energy()))
+    }
+
+    pub fn pheromones(&self) -> &Pheromones {
+        &self.state.pheromones
+    }
+
+    /// Como a iteração mais recente terminou, se alguma já terminou.
+    pub fn snapshot(&self) -> Option<IterationSnapshot> {
+        Some(IterationSnapshot {
+            stats: *self.state.iterations.last()?,
+            best_conformation: self.state.best_conformation.clone(),
+            best: self.state.best_energy(),
+            stop_reason: self.stop_reason
+        })
+    }
+
+    /// Inclui `conformation` entre as formigas da próxima iteração: ela concorre a melhor
+    /// conformação e deposita feromônio como as outras. Precisa estar completa, na mesma rede e
+    /// caber na proteína da busca.
+    pub fn inject(&mut self, conformation: &Conformation) -> Result<(), InjectError> {
+        if conformation.config().lattice != self.config.lattice {
+            return Err(InjectError::Lattice { found: conformation.config().lattice, expected: self.config.lattice });
+        }
+
+        let directions: Option<Vec<Direction>> = conformation.directions().iter().copied().collect();
+        let conformation = directions
+            .and_then(|directions| Conformation::from_directions(&self.protein, self.config, &directions))
+            .ok_or(InjectError::Invalid)?;
+
+        self.injected.push(conformation);
+        Ok(())
+    }
+
+    /// Andamento da busca (veja `StopCriteria::fraction`).
+    pub fn progress(&self) -> Progress {
+        let iterations = self.state.iteration();
+        let elapsed = self.elapsed();
+
+        let fraction = if self.is_finished() {
+            1.0
+        } else {
+            self.config.stop.fraction(iterations, self.config.max_iter, elapsed, self.state.evaluations)
+        };
+
+        Progress::new(iterations, fraction, elapsed, self.state.best_energy())
+    }
+
+    /// Para a busca quando `token` for cancelado (veja `CancellationToken`).
+    pub fn with_cancellation(self, token: CancellationToken) -> Self {
+        Self { cancel: Some(token), ..self }
+    }
+
+    /// Chama `on_progress` ao fim de cada iteração.
+    pub fn with_progress(self, on_progress: impl FnMut(Progress) + Send + 'static) -> Self {
+        Self { on_progress: Some(Box::new(on_progress)), ..self }
+    }
+
+    /// Encerra a busca com `StopReason::Cancelled` se o cancelamento foi pedido. Retorna se a busca foi cancelada.
+    pub fn check_cancelled(&mut self) -> bool {
+        if self.stop_reason.is_none() && self.cancel.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            self.stop_reason = Some(StopReason::Cancelled);
+        }
+
+        self.stop_reason == Some(StopReason::Cancelled)
+    }
+
+    /// Critério que encerrou a busca, se algum foi atingido.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.stop_reason
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.stop_reason.is_some() || self.state.iteration() >= self.config.max_iter
+    }
+
+    /// Tempo total, somando o de antes de continuar de um checkpoint.
+    pub fn elapsed(&self) -> Duration {
+        self.state.elapsed + self.start.elapsed()
+    }
+
+    /// Executa a próxima iteração com as formigas em paralelo. Retorna `None` se a busca já terminou.
+    pub fn step<L: ACOLogger + ?Sized>(&mut self, logger: &mut L) -> Option<&IterationStats> {
+        if self.check_cancelled() || self.is_finished() {
+            return None;
+        }
+
+        let mut ants = self.start_iteration();
+
+        if self.state.pheromones.has_local_update() {
+            /* Cada formiga vê a atualização local das anteriores */
+            for ant in &mut ants {
+                if self.cancel.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                    break;
+                }
+                self.run_ant(ant);
+            }
+        } else {
+            let pheromones = &self.state.pheromones;
+            let cancel = self.cancel.as_ref();
+
+            ants.par_iter_mut().for_each(|ant| {
+                if !cancel.is_some_and(CancellationToken::is_cancelled) {
+                    ant.run(pheromones);
+                }
+            });
+        }
+
+        if self.check_cancelled() {
+            return None;
+        }
+
+        let iteration = self.state.iteration();
+        for ant in &ants {
+            for event in ant.events.iter().filter_map(|step| step.event(iteration, ant.index)) {
+                logger.log(&event);
+            }
+            logger.log(&ant.finished_event(iteration));
+        }
+
+        self.finish_iteration(ants);
+        for event in self.iteration_events() {
+            logger.log(&event);
+        }
+
+        self.state.iterations.last()
+    }
+
+    /// Executa até o fim, como `aco_protein_folding_2dhp`.
+    pub fn run<L: ACOLogger + ?Sized>(mut self, logger: &mut L) -> RunReport {
+        logger.log(&self.start_event());
+        while self.step(logger).is_some() {}
+
+        let report = self.into_report();
+        logger.log(&Event::RunEnd { report: &report });
+        report
+    }
+
+    /// Evento do início de uma execução, para quem executa a busca passo a passo.
+    pub fn start_event(&self) -> Event<'_> {
+        Event::RunStart {
+            protein: &self.protein,
+            config: self.config,
+            iteration: self.state.iteration(),
+            pheromones: &self.state.pheromones
+        }
+    }
+
+    /// Eventos do fim da última iteração concluída, como enviados por `step`.
+    pub fn iteration_events(&self) -> Vec<Event<'_>> {
+        let Some(stats) = self.state.iterations.last() else {
+            return Vec::new();
+        };
+
+        vec![
+            Event::PheromoneUpdate { iteration: stats.iteration, pheromones: &self.state.pheromones },
+            Event::Iteration { stats, best: &self.state.best_conformation, pheromones: &self.state.pheromones }
+        ]
+    }
+
+    /// Como `Ant::advance`, aplicando a atualização local do ACS quando a formiga termina.
+    pub fn advance(&mut self, ant: &mut Ant) -> AntStep {
+        let finished = ant.fitness.is_some();
+        let step = ant.advance(&self.state.pheromones);
+
+        if step == AntStep::Finished && !finished {
+            self.state.pheromones.local_update(&ant.conformation);
+        }
+
+        step
+    }
+
+    /* Executa a formiga até o fim, se ainda não terminou */
+    fn run_ant(&mut self, ant: &mut Ant) {
+        if ant.fitness.is_none() {
+            ant.run(&self.state.pheromones);
+            self.state.pheromones.local_update(&ant.conformation);
+        }
+    }
+
+    /// Formigas da próxima iteração, para executar na ordem com `AcoEngine::advance`.
+    pub fn start_iteration(&mut self) -> Vec<Ant> {
+        self.iteration_start = Instant::now();
+
+        let iteration = self.state.iteration();
+        (0..self.config.ant_count).map(|ant| Ant::new(&self.protein, self.config, iteration, ant)).collect()
+    }
+
+    /// Atualiza a melhor conformação e os feromônios com as formigas de `start_iteration` e as
+    /// conformações injetadas, terminando as formigas que ainda não terminaram, e verifica os
+    /// critérios de parada.
+    pub fn finish_iteration(&mut self, mut ants: Vec<Ant>) -> &IterationStats {
+        let iteration = self.state.iteration();
+
+        for ant in &mut ants {
+            self.run_ant(ant);
+        }
+
+        let construction = ants.iter().map(|ant| ant.construction).sum();
+        let improvements = ants.iter().map(|ant| ant.improvements).sum();
+
+        let mut fitnesses: Vec<f64> = ants.iter().map(|ant| ant.fitness.unwrap()).collect();
+        let mut conformations: Vec<_> = ants.into_iter().map(|ant| ant.conformation).collect();
+
+        /* As conformações injetadas entram como formigas a mais */
+        for conf in mem::take(&mut self.injected) {
+            fitnesses.push(conf.eval());
+            conformations.push(conf);
+        }
+
+        // Atualiza melhor solução
+        for (conf, &fit) in conformations.iter().zip(&fitnesses) {
+            if fit > self.state.best {
+                self.state.best = fit;
+                self.state.best_conformation = conf.clone();
+                self.state.best_iteration = Some(iteration);
+            }
+        }
+
+        self.state.evaluations += conformations.iter().map(Conformation::evaluations).sum::<u64>();
+
+        self.state.pheromones.update(&conformations, &self.state.best_conformation);
+
+        let stats = IterationStats::new(
+            iteration,
+            self.state.best,
+            &fitnesses,
+            construction,
+            improvements,
+            self.state.pheromones.entropy(),
+            self.iteration_start.elapsed()
+        );
+        self.state.iterations.push(stats);
+
+        let progress = SearchProgress {
+            best: self.state.best_energy(),
+            elapsed: self.elapsed(),
+            evaluations: self.state.evaluations,
+            stagnation: self.state.best_iteration.map_or(iteration + 1, |best_iteration| iteration - best_iteration),
+            pheromone_entropy: stats.pheromone_entropy
+        };
+
+        self.stop_reason = self.config.stop.check(&progress);
+
+        let progress = self.progress();
+        if let Some(on_progress) = &mut self.on_progress {
+            on_progress(progress);
+        }
+
+        self.state.iterations.last().unwrap()
+    }
+
+    pub fn into_report(self) -> RunReport {
+        RunReport {
+            elapsed: self.elapsed(),
+            stop_reason: self.stop_reason.unwrap_or(StopReason::MaxIterations),
+            best_conformation: self.state.best_conformation,
+            best: Energy::from_fitness(self.state.best),
+            best_iteration: self.state.best_iteration,
+            iterations: self.state.iterations,
+            evaluations: self.state.evaluations,
+            pheromones: self.state.pheromones
+        }
+    }
+}
+
+/* O iterador não tem logger: os eventos que `step` registraria ficam no `IterationSnapshot` */
+impl Iterator for AcoEngine {
+    type Item = IterationSnapshot;
+
+    fn next(&mut self) -> Option<IterationSnapshot> {
+        self.step(&mut ())?;
+        self.snapshot()
+    }
+}
+
+/// Resultado de uma iteração, como produzido pelo `AcoEngine` como iterador.
+#[derive(Debug, Clone)]
+pub struct IterationSnapshot {
+    pub stats: IterationStats,
+    pub best_conformation: Conformation,
+    pub best: Energy,
+    /* Critério que encerrou a busca nesta iteração, se algum */
+    pub stop_reason: Option<StopReason>
+}
+
+#[derive(Debug)]
+pub enum InjectError {
+    Lattice { found: LatticeKind, expected: LatticeKind },
+    /* Incompleta, de outro tamanho ou com sobreposição na proteína da busca */
+    Invalid
+}
+
+impl fmt::Display for InjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InjectError::Lattice { found, expected } => {
+                write!(f, "conformação na rede {:?}, mas a busca usa a rede {:?}", found, expected)
+            }
+            InjectError::Invalid => write!(f, "conformação incompleta ou inválida para a proteína da busca")
+        }
+    }
+}
+
+impl Error for InjectError {}
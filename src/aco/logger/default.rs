@@ -1,44 +1,111 @@
 use colored::Colorize;
 
-use crate::{aco::{config::ACOConfig, logger::ACOLogger}, conformation::Conformation};
+use crate::aco::logger::{optimum_suffix, ACOLogger, Event};
 
+/// Quais eventos o `DefaultLogger` imprime.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum DefaultLogger {
+pub enum LogLevel {
     Iteration,
     Ant,
     None
 }
 
+/// Textos impressos pelo `DefaultLogger`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Labels {
+    pub iteration: &'static str,
+    pub best_energy: &'static str,
+    pub ants: &'static str,
+    pub evaporation: &'static str,
+    pub ant: &'static str,
+    pub energy: &'static str,
+    pub optimum: &'static str,
+    pub error: &'static str
+}
+
+impl Labels {
+    pub const PORTUGUESE: Labels = Labels {
+        iteration: "ITERAÇÃO",
+        best_energy: "Melhor energia",
+        ants: "ants",
+        evaporation: "Evaporação",
+        ant: "Ant",
+        energy: "Energia",
+        optimum: "ótimo",
+        error: "erro"
+    };
+
+    pub const ENGLISH: Labels = Labels {
+        iteration: "ITERATION",
+        best_energy: "Best energy",
+        ants: "ants",
+        evaporation: "Evaporation",
+        ant: "Ant",
+        energy: "Energy",
+        optimum: "optimum",
+        error: "error"
+    };
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Labels::PORTUGUESE
+    }
+}
+
+/// Imprime as iterações (e as formigas, em `LogLevel::Ant`) no terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultLogger {
+    pub level: LogLevel,
+    pub labels: Labels
+}
+
+impl DefaultLogger {
+    pub fn new(level: LogLevel) -> Self {
+        Self { level, labels: Labels::default() }
+    }
+
+    pub fn with_labels(self, labels: Labels) -> Self {
+        Self { labels, ..self }
+    }
+}
+
 impl ACOLogger for DefaultLogger {
-    fn log_iteration(&self, config: ACOConfig, iter: u16, _: &Conformation, fit: f64) {
-        match self {
-            DefaultLogger::Iteration | DefaultLogger::Ant => {
+    fn log(&mut self, event: &Event<'_>) {
+        match *event {
+            Event::Iteration { stats, best, .. } if self.level != LogLevel::None => {
+                let config = best.config();
+                let energy = stats.best_so_far;
+
                 println!("{}", "=".repeat(93).blue().bold());
                 println!(
-                    "{} {:0>2}/{:0>2}  |  Melhor Fitness: {}  |  {} ants  |  Evaporação: {:.2}  |  α: {:.2} β: {:.2}",
-                    " ITERAÇÃO:".bold().blue(),
-                    (iter + 1).to_string().bold().yellow(),
+                    " {}: {:0>2}/{:0>2}  |  {}: {}{}  |  {} {}  |  {}: {:.2}  |  α: {:.2} β: {:.2}",
+                    self.labels.iteration.bold().blue(),
+                    (stats.iteration + 1).to_string().bold().yellow(),
                     config.max_iter.to_string().bold().yellow(),
-                    fit.to_string().green().bold(),
+                    self.labels.best_energy,
+                    energy.to_string().green().bold(),
+                    optimum_suffix(config, energy, &self.labels),
                     config.ant_count.to_string().bold().cyan(),
+                    self.labels.ants,
+                    self.labels.evaporation,
                     config.evaporation,
                     config.alpha,
                     config.beta
                 );
                 println!("{}", "=".repeat(93).blue().bold());
             }
+            Event::Ant { ant, conformation, energy, .. } if self.level == LogLevel::Ant => {
+                println!(
+                    " {}: {:0>2}  |  {}: {}{}",
+                    self.labels.ant.magenta(),
+                    ant.to_string().yellow().bold(),
+                    self.labels.energy,
+                    energy.to_string().green().bold(),
+                    optimum_suffix(conformation.config(), energy, &self.labels)
+                );
+            }
             _ => {}
         }
     }
-
-    fn log_ant(&self, _: ACOConfig, ant: u16, _: &Conformation, fit: f64) {
-        if let DefaultLogger::Ant = self {
-            println!(
-                " {}: {:0>2}  |  Fitness: {}",
-                "Ant".magenta(),
-                ant.to_string().yellow().bold(),
-                fit.to_string().green().bold()
-            );
-        }
-    }
-}
\ No newline at end of file
+}
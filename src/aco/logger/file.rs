@@ -0,0 +1,322 @@
+use std::{error::Error, fmt, fs::File, io::{self, BufRead, BufReader, BufWriter, Lines, Write}, path::{Path, PathBuf}, time::Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{aco::{config::ACOConfig, logger::{ACOLogger, Event}, report::IterationStats, stop::StopReason}, conformation::Conformation, pheromones::{PheromoneSnapshot, Pheromones, SnapshotError}, protein::{protein_to_string, Energy, Protein}};
+
+/* Incrementar sempre que um campo de `EventRecord` mudar */
+pub const EVENT_LOG_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    RunStart,
+    Step,
+    Backtrack,
+    Improvement,
+    Ant,
+    PheromoneUpdate,
+    Iteration,
+    RunEnd
+}
+
+/// Um `Event` como gravado pelo `FileLogger`, uma linha JSON por evento. Os campos que o
+/// evento não tem ficam nulos.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub format_version: u32,
+    pub kind: EventKind,
+    /* Segundos desde o `RunStart` */
+    pub timestamp: f64,
+    pub iteration: Option<u16>,
+    pub ant: Option<u16>,
+    /* Da formiga em `Ant` e `Improvement`; a melhor até agora em `Iteration` e `RunEnd` */
+    pub energy: Option<Energy>,
+    /* Em `Step`, só as direções já escolhidas (veja `Conformation::to_partial_string`) */
+    pub conformation: Option<String>,
+    pub undone: Option<u32>,
+    pub stats: Option<IterationStats>,
+    pub stop_reason: Option<StopReason>,
+    /* Só em `RunStart` */
+    pub sequence: Option<String>,
+    pub energy_model: Option<String>,
+    pub config: Option<ACOConfig>,
+    /* Em `RunStart`, `PheromoneUpdate` e `RunEnd`, se o logger foi criado com `with_pheromones` */
+    pub pheromones: Option<PheromoneSnapshot>
+}
+
+impl EventRecord {
+    fn new(kind: EventKind, timestamp: f64) -> Self {
+        Self {
+            format_version: EVENT_LOG_FORMAT_VERSION,
+            kind,
+            timestamp,
+            iteration: None,
+            ant: None,
+            energy: None,
+            conformation: None,
+            undone: None,
+            stats: None,
+            stop_reason: None,
+            sequence: None,
+            energy_model: None,
+            config: None,
+            pheromones: None
+        }
+    }
+
+    /// A conformação do evento, montada para `protein` com a `config` do `RunStart`. Em `Step`,
+    /// ela pode estar incompleta.
+    pub fn conformation(&self, protein: &Protein, config: ACOConfig) -> Option<Conformation> {
+        Conformation::from_partial_relative_str(protein, config, self.conformation.as_deref()?)
+    }
+
+    /// Os feromônios do evento, se foram gravados.
+    pub fn pheromones(&self, protein: &Protein, config: ACOConfig) -> Option<Result<Pheromones, SnapshotError>> {
+        Some(Pheromones::from_snapshot(self.pheromones.as_ref()?, protein, config))
+    }
+}
+
+/// Grava os eventos da busca em JSON Lines, para analisar ou reproduzir a execução depois
+/// (veja `EventLogReader`). Erros de escrita são guardados e retornados por `finish`.
+#[derive(Debug)]
+pub struct FileLogger {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    steps: bool,
+    pheromones: bool,
+    /* Proteína do `RunStart`, para os snapshots de feromônios */
+    protein: Option<Protein>,
+    start: Instant,
+    error: Option<io::Error>
+}
+
+impl FileLogger {
+    /// Cria (ou trunca) o arquivo em `path`.
+    pub fn create(path: &Path) -> Result<Self, EventLogError> {
+        let file = File::create(path).map_err(|e| EventLogError::Io(path.to_path_buf(), e))?;
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+            path: path.to_path_buf(),
+            steps: false,
+            pheromones: false,
+            protein: None,
+            start: Instant::now(),
+            error: None
+        })
+    }
+
+    /// Grava também os eventos `Step`, um por aminoácido colocado. O arquivo fica bem maior.
+    pub fn with_steps(self, steps: bool) -> Self {
+        Self { steps, ..self }
+    }
+
+    /// Grava um snapshot dos feromônios em cada atualização.
+    pub fn with_pheromones(self, pheromones: bool) -> Self {
+        Self { pheromones, ..self }
+    }
+
+    /// Grava o que ficou no buffer e retorna o primeiro erro de escrita, se houve.
+    pub fn finish(mut self) -> Result<(), EventLogError> {
+        let result = match self.error.take() {
+            Some(e) => Err(e),
+            None => self.writer.flush()
+        };
+
+        result.map_err(|e| EventLogError::Io(self.path, e))
+    }
+
+    fn snapshot(&self, pheromones: &Pheromones, best: Option<&Conformation>) -> Option<PheromoneSnapshot> {
+        Some(pheromones.snapshot(self.protein.as_ref().filter(|_| self.pheromones)?, best))
+    }
+
+    fn record(&self, event: &Event<'_>) -> Option<EventRecord> {
+        let timestamp = self.start.elapsed().as_secs_f64();
+
+        let record = match *event {
+            Event::RunStart { protein, config, iteration, pheromones } => EventRecord {
+                iteration: Some(iteration),
+                sequence: Some(protein_to_string(protein.residues())),
+                energy_model: Some(protein.model().name().to_string()),
+                config: Some(config),
+                pheromones: self.snapshot(pheromones, None),
+                ..EventRecord::new(EventKind::RunStart, timestamp)
+            },
+            Event::Step { iteration, ant, conformation } => {
+                if !self.steps {
+                    return None;
+                }
+
+                EventRecord {
+                    iteration: Some(iteration),
+                    ant: Some(ant),
+                    conformation: Some(conformation.to_partial_string()),
+                    ..EventRecord::new(EventKind::Step, timestamp)
+                }
+            }
+            Event::Backtrack { iteration, ant, undone } => EventRecord {
+                iteration: Some(iteration),
+                ant: Some(ant),
+                undone: Some(undone),
+                ..EventRecord::new(EventKind::Backtrack, timestamp)
+            },
+            Event::Improvement { iteration, ant, energy } => EventRecord {
+                iteration: Some(iteration),
+                ant: Some(ant),
+                energy: Some(energy),
+                ..EventRecord::new(EventKind::Improvement, timestamp)
+            },
+            Event::Ant { iteration, ant, conformation, energy } => EventRecord {
+                iteration: Some(iteration),
+                ant: Some(ant),
+                energy: Some(energy),
+                conformation: Some(conformation.to_string()),
+                ..EventRecord::new(EventKind::Ant, timestamp)
+            },
+            Event::PheromoneUpdate { iteration, pheromones } => EventRecord {
+                iteration: Some(iteration),
+                pheromones: self.snapshot(pheromones, None),
+                ..EventRecord::new(EventKind::PheromoneUpdate, timestamp)
+            },
+            Event::Iteration { stats, best, .. } => EventRecord {
+                iteration: Some(stats.iteration),
+                energy: Some(stats.best_so_far),
+                conformation: finished(best),
+                stats: Some(*stats),
+                ..EventRecord::new(EventKind::Iteration, timestamp)
+            },
+            Event::RunEnd { report } => EventRecord {
+                iteration: report.iterations.last().map(|stats| stats.iteration),
+                energy: Some(report.best),
+                conformation: finished(&report.best_conformation),
+                stop_reason: Some(report.stop_reason),
+                pheromones: self.snapshot(&report.pheromones, report.best_conformation.is_fully_grown().then_some(&report.best_conformation)),
+                ..EventRecord::new(EventKind::RunEnd, timestamp)
+            }
+        };
+
+        Some(record)
+    }
+
+    fn write(&mut self, record: &EventRecord) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+impl ACOLogger for FileLogger {
+    fn log(&mut self, event: &Event<'_>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Event::RunStart { protein, .. } = *event {
+            self.protein = Some(protein.clone());
+            self.start = Instant::now();
+        }
+
+        let Some(record) = self.record(event) else {
+            return;
+        };
+
+        let mut result = self.write(&record);
+        if matches!(event, Event::RunEnd { .. }) {
+            result = result.and_then(|_| self.writer.flush());
+        }
+
+        self.error = result.err();
+    }
+}
+
+/* A melhor conformação ainda não existe numa execução cancelada antes da primeira iteração */
+fn finished(conformation: &Conformation) -> Option<String> {
+    conformation.is_fully_grown().then(|| conformation.to_string())
+}
+
+/// Lê os eventos gravados pelo `FileLogger`, na ordem em que aconteceram.
+pub struct EventLogReader<R> {
+    lines: Lines<R>,
+    path: PathBuf,
+    line: usize
+}
+
+impl EventLogReader<BufReader<File>> {
+    pub fn open(path: &Path) -> Result<Self, EventLogError> {
+        let file = File::open(path).map_err(|e| EventLogError::Io(path.to_path_buf(), e))?;
+        Ok(Self::new(BufReader::new(file), path))
+    }
+}
+
+impl<R: BufRead> EventLogReader<R> {
+    /// `path` só aparece nas mensagens de erro.
+    pub fn new(reader: R, path: &Path) -> Self {
+        Self { lines: reader.lines(), path: path.to_path_buf(), line: 0 }
+    }
+
+    fn parse(&self, line: &str) -> Result<EventRecord, EventLogError> {
+        let record: EventRecord = serde_json::from_str(line)
+            .map_err(|error| EventLogError::Json { path: self.path.clone(), line: self.line, error })?;
+
+        if record.format_version != EVENT_LOG_FORMAT_VERSION {
+            return Err(EventLogError::Version { found: record.format_version, expected: EVENT_LOG_FORMAT_VERSION });
+        }
+
+        Ok(record)
+    }
+}
+
+impl<R: BufRead> Iterator for EventLogReader<R> {
+    type Item = Result<EventRecord, EventLogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(EventLogError::Io(self.path.clone(), e)))
+            };
+
+            if !line.trim().is_empty() {
+                return Some(self.parse(&line));
+            }
+        }
+    }
+}
+
+/// Lê todos os eventos do arquivo de uma vez.
+pub fn read_event_log(path: &Path) -> Result<Vec<EventRecord>, EventLogError> {
+    EventLogReader::open(path)?.collect()
+}
+
+#[derive(Debug)]
+pub enum EventLogError {
+    Io(PathBuf, io::Error),
+    Json { path: PathBuf, line: usize, error: serde_json::Error },
+    Version { found: u32, expected: u32 }
+}
+
+impl fmt::Display for EventLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventLogError::Io(path, e) => write!(f, "erro ao acessar '{}': {}", path.display(), e),
+            EventLogError::Json { path, line, error } => {
+                write!(f, "{}, linha {}: JSON inválido: {}", path.display(), line, error)
+            }
+            EventLogError::Version { found, expected } => {
+                write!(f, "versão {} do log de eventos não é suportada (esperada: {})", found, expected)
+            }
+        }
+    }
+}
+
+impl Error for EventLogError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EventLogError::Io(_, e) => Some(e),
+            EventLogError::Json { error, .. } => Some(error),
+            EventLogError::Version { .. } => None
+        }
+    }
+}
@@ -1,50 +1,49 @@
-use crate::{aco::{config::ACOConfig, logger::AsyncACOLogger}, conformation::Conformation, pheromones::Pheromones};
+use crate::{aco::logger::{AsyncACOLogger, Event}, pheromones::Pheromones, protein::Energy};
 
+/// Quais eventos desenham um novo quadro.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum MacroquadLogger {
+pub enum DrawOn {
     Iteration,
     Ant,
     Change,
     None
 }
 
-impl AsyncACOLogger for MacroquadLogger {
-    async fn log_iteration(
-        &self,
-        _: ACOConfig,
-        iter: u16,
-        conformation: &Conformation,
-        fit: f64,
-        pheromones: &Pheromones
-    ) {
-        if let MacroquadLogger::Iteration = self { 
-            conformation.draw(iter, fit, Some(pheromones)).await;
-        }
-    }
+/// Desenha a conformação do evento e os feromônios mais recentes.
+#[derive(Debug, Clone)]
+pub struct MacroquadLogger {
+    draw_on: DrawOn,
+    /* Melhor energia da última iteração, mostrada nos quadros das formigas */
+    best: Energy,
+    pheromones: Option<Pheromones>
+}
 
-    async fn log_ant(
-        &self,
-        _: ACOConfig,
-        iter: u16,
-        conformation: &Conformation,
-        fit: f64,
-        pheromones: &Pheromones
-    ) {
-        if let MacroquadLogger::Ant = self { 
-            conformation.draw(iter, fit, Some(pheromones)).await;
-        }
+impl MacroquadLogger {
+    pub fn new(draw_on: DrawOn) -> Self {
+        Self { draw_on, best: Energy::from_fitness(f64::NEG_INFINITY), pheromones: None }
     }
+}
+
+impl AsyncACOLogger for MacroquadLogger {
+    async fn log_async(&mut self, event: &Event<'_>) {
+        match *event {
+            Event::RunStart { pheromones, .. } | Event::PheromoneUpdate { pheromones, .. } => {
+                self.pheromones = Some(pheromones.clone());
+            }
+            Event::Iteration { stats, best, pheromones } => {
+                self.best = stats.best_so_far;
 
-    async fn log_change(
-        &self,
-        _: ACOConfig,
-        iter: u16,
-        conformation: &Conformation,
-        fit: f64,
-        pheromones: &Pheromones
-    ) {
-        if let MacroquadLogger::Change = self { 
-            conformation.draw(iter, fit, Some(pheromones)).await;
+                if self.draw_on == DrawOn::Iteration {
+                    best.draw(stats.iteration, self.best, Some(pheromones)).await;
+                }
+            }
+            Event::Ant { iteration, conformation, .. } if self.draw_on == DrawOn::Ant => {
+                conformation.draw(iteration, self.best, self.pheromones.as_ref()).await;
+            }
+            Event::Step { iteration, conformation, .. } if self.draw_on == DrawOn::Change => {
+                conformation.draw(iteration, self.best, self.pheromones.as_ref()).await;
+            }
+            _ => {}
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+use crate::{aco::logger::{ACOLogger, Event}, protein::Energy};
+
+/// Conta os eventos da busca, para exportar como métricas junto com outros loggers.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsLogger {
+    pub runs: u32,
+    pub iterations: u32,
+    pub ants: u64,
+    pub backtracks: u64,
+    pub undone_steps: u64,
+    pub improvements: u64,
+    pub pheromone_updates: u32,
+    /* Melhor energia relatada nas iterações */
+    pub best: Option<Energy>
+}
+
+impl ACOLogger for MetricsLogger {
+    fn log(&mut self, event: &Event<'_>) {
+        match *event {
+            Event::RunStart { .. } => self.runs += 1,
+            Event::Step { .. } | Event::RunEnd { .. } => {}
+            Event::Backtrack { undone, .. } => {
+                self.backtracks += 1;
+                self.undone_steps += undone as u64;
+            }
+            Event::Improvement { .. } => self.improvements += 1,
+            Event::Ant { .. } => self.ants += 1,
+            Event::PheromoneUpdate { .. } => self.pheromone_updates += 1,
+            Event::Iteration { stats, .. } => {
+                self.iterations += 1;
+                self.best = Some(stats.best_so_far);
+            }
+        }
+    }
+}
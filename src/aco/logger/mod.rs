@@ -1,38 +1,103 @@
-use crate::{aco::config::ACOConfig, conformation::Conformation, pheromones::Pheromones};
+use crate::{aco::{config::ACOConfig, logger::default::Labels, report::{IterationStats, RunReport}}, conformation::Conformation, pheromones::Pheromones, protein::{Energy, Protein}};
 
 pub mod default;
+pub mod file;
+#[cfg(feature = "visual")]
 pub mod macroquad;
+pub mod metrics;
+pub mod replay;
 
-pub trait ACOLogger: Clone + Copy {
-    fn log_iteration(&self, config: ACOConfig, iter: u16, conformation: &Conformation, fit: f64);
-    fn log_ant(&self, config: ACOConfig, ant: u16, conformation: &Conformation, fit: f64);
-}
-
-pub trait AsyncACOLogger: Clone + Copy {
-    async fn log_iteration(
-        &self,
-        config: ACOConfig,
-        iter: u16,
-        conformation: &Conformation,
-        fit: f64,
-        pheromones: &Pheromones
-    );
-    
-    async fn log_ant(
-        &self,
-        config: ACOConfig,
-        iter: u16,
-        conformation: &Conformation,
-        fit: f64,
-        pheromones: &Pheromones
-    );
-
-    async fn log_change(
-        &self,
-        config: ACOConfig,
-        iter: u16,
-        conformation: &Conformation,
-        fit: f64,
-        pheromones: &Pheromones
-    );
-}
\ No newline at end of file
+/// Evento da busca enviado aos loggers, na ordem em que acontecem. Os eventos das formigas
+/// chegam em ordem de formiga, depois que todas terminaram, exceto em `AcoEngine::run_async`,
+/// que os envia durante a construção.
+#[derive(Debug, Clone, Copy)]
+pub enum Event<'a> {
+    /* `iteration` é a primeira iteração a executar: maior que zero ao continuar de um checkpoint */
+    RunStart { protein: &'a Protein, config: ACOConfig, iteration: u16, pheromones: &'a Pheromones },
+    /* A conformação de uma formiga mudou; só em `AcoEngine::run_async`, para desenhar cada passo */
+    Step { iteration: u16, ant: u16, conformation: &'a Conformation },
+    /* A formiga ficou sem saída e desfez `undone` passos */
+    Backtrack { iteration: u16, ant: u16, undone: u32 },
+    /* A busca local melhorou a conformação da formiga */
+    Improvement { iteration: u16, ant: u16, energy: Energy },
+    /* A formiga terminou a conformação e a busca local */
+    Ant { iteration: u16, ant: u16, conformation: &'a Conformation, energy: Energy },
+    PheromoneUpdate { iteration: u16, pheromones: &'a Pheromones },
+    Iteration { stats: &'a IterationStats, best: &'a Conformation, pheromones: &'a Pheromones },
+    RunEnd { report: &'a RunReport }
+}
+
+/// Observador da busca. Para enviar os eventos a vários loggers, use uma tupla, um `Vec` ou
+/// `Vec<Box<dyn ACOLogger>>`; `()` não registra nada.
+pub trait ACOLogger {
+    fn log(&mut self, event: &Event<'_>);
+}
+
+/// Como `ACOLogger`, para loggers que precisam esperar, como os que desenham um quadro por
+/// evento. Todo `ACOLogger` também é um `AsyncACOLogger`.
+/* Os futures do macroquad não são Send, então não há bound a exigir */
+#[allow(async_fn_in_trait)]
+pub trait AsyncACOLogger {
+    async fn log_async(&mut self, event: &Event<'_>);
+}
+
+impl<L: ACOLogger + ?Sized> AsyncACOLogger for L {
+    async fn log_async(&mut self, event: &Event<'_>) {
+        self.log(event);
+    }
+}
+
+impl ACOLogger for () {
+    fn log(&mut self, _: &Event<'_>) {}
+}
+
+impl<L: ACOLogger + ?Sized> ACOLogger for &mut L {
+    fn log(&mut self, event: &Event<'_>) {
+        (**self).log(event);
+    }
+}
+
+impl<L: ACOLogger + ?Sized> ACOLogger for Box<L> {
+    fn log(&mut self, event: &Event<'_>) {
+        (**self).log(event);
+    }
+}
+
+impl<L: ACOLogger> ACOLogger for Option<L> {
+    fn log(&mut self, event: &Event<'_>) {
+        if let Some(logger) = self {
+            logger.log(event);
+        }
+    }
+}
+
+impl<L: ACOLogger> ACOLogger for Vec<L> {
+    fn log(&mut self, event: &Event<'_>) {
+        for logger in self {
+            logger.log(event);
+        }
+    }
+}
+
+impl<A: ACOLogger, B: ACOLogger> ACOLogger for (A, B) {
+    fn log(&mut self, event: &Event<'_>) {
+        self.0.log(event);
+        self.1.log(event);
+    }
+}
+
+impl<A: ACOLogger, B: ACOLogger, C: ACOLogger> ACOLogger for (A, B, C) {
+    fn log(&mut self, event: &Event<'_>) {
+        self.0.log(event);
+        self.1.log(event);
+        self.2.log(event);
+    }
+}
+
+/// Ótimo conhecido e erro relativo a ele, quando a configuração tem um ótimo.
+pub(crate) fn optimum_suffix(config: ACOConfig, energy: Energy, labels: &Labels) -> String {
+    match config.optimum.and_then(|optimum| Some((optimum, energy.relative_error_to_optimum(optimum)?))) {
+        Some((optimum, error)) => format!(" ({}: {}, {}: {:.1}%)", labels.optimum, optimum, labels.error, error * 100.0),
+        None => String::new()
+    }
+}
@@ -0,0 +1,156 @@
+use std::{error::Error, fmt, path::Path, sync::Arc};
+
+use crate::{aco::{config::ACOConfig, logger::file::{EventKind, EventLogError, EventLogReader, EventRecord}}, conformation::Conformation, pheromones::{Pheromones, SnapshotError}, protein::{parse_protein_sequence_with, Energy, EnergyModel, ParseError, Protein}};
+
+/// Uma execução gravada pelo `FileLogger`, como quadros para reproduzir: um por `Step`, `Ant`
+/// e `Iteration` gravado.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub protein: Protein,
+    pub config: ACOConfig,
+    pub frames: Vec<Frame>,
+    pheromones: Vec<Pheromones>
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub kind: EventKind,
+    pub iteration: u16,
+    pub ant: Option<u16>,
+    pub conformation: Conformation,
+    /* Melhor energia da última iteração até este quadro, como no `MacroquadLogger` */
+    pub best: Energy,
+    /* A melhor energia melhorou nesta iteração */
+    pub improved: bool,
+    /* Índice em `Replay::pheromones` dos últimos feromônios gravados */
+    pheromones: Option<usize>
+}
+
+impl Replay {
+    /// Lê o log de eventos em `path`, gravado com a proteína no modelo `model`.
+    pub fn load(path: &Path, model: &Arc<EnergyModel>) -> Result<Self, ReplayError> {
+        let records = EventLogReader::open(path)?.collect::<Result<Vec<_>, _>>()?;
+        Replay::from_records(records, model)
+    }
+
+    pub fn from_records(records: impl IntoIterator<Item = EventRecord>, model: &Arc<EnergyModel>) -> Result<Self, ReplayError> {
+        let mut records = records.into_iter();
+
+        let start = records.next()
+            .filter(|record| record.kind == EventKind::RunStart)
+            .ok_or(ReplayError::MissingStart)?;
+
+        let (Some(sequence), Some(energy_model), Some(config)) = (start.sequence.as_deref(), start.energy_model.as_deref(), start.config)
+        else {
+            return Err(ReplayError::MissingStart);
+        };
+
+        if energy_model != model.name() {
+            return Err(ReplayError::EnergyModel { found: energy_model.to_string(), expected: model.name().to_string() });
+        }
+
+        let protein = parse_protein_sequence_with(sequence, model)?;
+        let mut replay = Replay { protein, config, frames: Vec::new(), pheromones: Vec::new() };
+        let mut best = Energy::from_fitness(f64::NEG_INFINITY);
+
+        for record in std::iter::once(start).chain(records) {
+            if let Some(pheromones) = record.pheromones(&replay.protein, config) {
+                replay.pheromones.push(pheromones?);
+            }
+
+            if !matches!(record.kind, EventKind::Step | EventKind::Ant | EventKind::Iteration) {
+                continue;
+            }
+
+            let Some(conformation) = record.conformation(&replay.protein, config) else {
+                continue;
+            };
+
+            /* Uma iteração só melhora se a energia for menor que a melhor até então */
+            let improved = record.kind == EventKind::Iteration && record.energy.is_some_and(|energy| energy < best);
+            if record.kind == EventKind::Iteration {
+                best = record.energy.unwrap_or(best);
+            }
+
+            replay.frames.push(Frame {
+                kind: record.kind,
+                iteration: record.iteration.unwrap_or_default(),
+                ant: record.ant,
+                conformation,
+                best,
+                improved,
+                pheromones: replay.pheromones.len().checked_sub(1)
+            });
+        }
+
+        Ok(replay)
+    }
+
+    pub fn pheromones(&self, frame: &Frame) -> Option<&Pheromones> {
+        self.pheromones.get(frame.pheromones?)
+    }
+
+    /// Próximo quadro, depois de `from`, em que a melhor energia melhorou; volta ao começo no fim.
+    pub fn next_improvement(&self, from: usize) -> Option<usize> {
+        let len = self.frames.len();
+        (1..=len).map(|offset| (from + offset) % len).find(|&i| self.frames[i].improved)
+    }
+
+    /// Quadro da iteração em que a busca encontrou a melhor energia.
+    pub fn best_improvement(&self) -> Option<usize> {
+        self.frames.iter().rposition(|frame| frame.improved)
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Log(EventLogError),
+    /* O log não começa com um `RunStart` completo */
+    MissingStart,
+    EnergyModel { found: String, expected: String },
+    Protein(ParseError),
+    Pheromones(SnapshotError)
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Log(e) => write!(f, "{}", e),
+            ReplayError::MissingStart => write!(f, "o log de eventos não começa com o início da execução"),
+            ReplayError::EnergyModel { found, expected } => {
+                write!(f, "execução gravada no modelo '{}', mas o modelo escolhido é '{}'", found, expected)
+            }
+            ReplayError::Protein(e) => write!(f, "sequência inválida no log de eventos: {}", e),
+            ReplayError::Pheromones(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReplayError::Log(e) => Some(e),
+            ReplayError::Protein(e) => Some(e),
+            ReplayError::Pheromones(e) => Some(e),
+            ReplayError::MissingStart | ReplayError::EnergyModel { .. } => None
+        }
+    }
+}
+
+impl From<EventLogError> for ReplayError {
+    fn from(e: EventLogError) -> Self {
+        ReplayError::Log(e)
+    }
+}
+
+impl From<ParseError> for ReplayError {
+    fn from(e: ParseError) -> Self {
+        ReplayError::Protein(e)
+    }
+}
+
+impl From<SnapshotError> for ReplayError {
+    fn from(e: SnapshotError) -> Self {
+        ReplayError::Pheromones(e)
+    }
+}
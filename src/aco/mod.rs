@@ -1,77 +1,56 @@
-use std::sync::{Arc, Mutex};
-
-use crate::{aco::{config::ACOConfig, logger::ACOLogger}, conformation::Conformation, pheromones::Pheromones, protein::Protein};
-use rand::{rng, rngs::ThreadRng};
-use rayon::prelude::*;
+use crate::{aco::{config::ACOConfig, engine::AcoEngine, logger::ACOLogger, report::RunReport}, conformation::Conformation, pheromones::Pheromones, protein::Protein};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 pub mod config;
 pub mod logger;
 pub mod async_aco;
+pub mod checkpoint;
+pub mod engine;
+pub mod report;
+pub mod stop;
+
+/// Estado inicial de uma execução: os feromônios e, se houver, a melhor conformação já conhecida.
+#[derive(Debug, Clone)]
+pub struct WarmStart {
+    pub pheromones: Pheromones,
+    pub best: Option<Conformation>
+}
+
+impl WarmStart {
+    /// Feromônios no valor inicial e nenhuma conformação, como numa execução nova.
+    pub fn cold(protein: &Protein, config: ACOConfig) -> Self {
+        Self { pheromones: Pheromones::new(protein, config), best: None }
+    }
+}
 
 pub fn aco_protein_folding_2dhp<L>(
     protein: &Protein,
     config: ACOConfig,
     logger: L
-) -> (Conformation, f64)
+) -> RunReport
 where
-    L: ACOLogger + Send + Sync + 'static,
+    L: ACOLogger,
 {
-    let mut pheromones = Pheromones::new(&protein, config);
-
-    let mut best_conformation = Conformation::new(protein, config);
-    let mut best = f64::NEG_INFINITY;
-
-    let thread_logger = Arc::new(Mutex::new(logger));
-
-    for iteration in 0..config.max_iter {        
-        // let conformations_zip: Vec<_> = (0..config.ant_count).into_iter()
-        let conformations_zip: Vec<_> = (0..config.ant_count).into_par_iter()
-            .map(|ant| {
-                let mut conf = Conformation::new(protein, config); /* Cria nova conformação */
-                let mut rng = rng();
-
-                while !conf.is_fully_grown() {
-                    if !conf.grow(&pheromones, &mut rng) {
-                        conf.rewind();
-                    }
-                }
-                
-                // Tenta melhorar solução encontrada
-                local_search_loop(&mut conf, config.no_impr_max, &mut rng);
-    
-                let fit = conf.eval(); /* Avalia para comparação */
-
-                thread_logger.lock().unwrap().log_ant(config, ant, &conf, fit);
-                                
-                (conf, fit)
-            }).collect();
-
-        // Atualiza melhor solução
-        let conformations: Vec<_> = conformations_zip.into_iter()
-            .map(|(conf, fit)| {
-                if fit > best {
-                    best = fit;
-                    best_conformation = conf.clone();
-                }
-                conf
-            })
-            .collect();
-
-        pheromones.update(&conformations);
-
-        thread_logger.lock().unwrap().log_iteration(config, iteration, &best_conformation, best);
-    }
+    aco_protein_folding_2dhp_from(protein, config, logger, WarmStart::cold(protein, config))
+}
 
-    (best_conformation, best)
+/// Como `aco_protein_folding_2dhp`, continuando a partir de `start` (veja `PheromoneSnapshot`).
+pub fn aco_protein_folding_2dhp_from<L>(
+    protein: &Protein,
+    config: ACOConfig,
+    mut logger: L,
+    start: WarmStart
+) -> RunReport
+where
+    L: ACOLogger,
+{
+    AcoEngine::with_start(protein, config, start).run(&mut logger)
 }
 
-fn local_search_loop(conformation: &mut Conformation, no_impr_max: u16, rng: &mut ThreadRng) {
-    let mut no_impr = 0;
-    while no_impr < no_impr_max {
-        if conformation.local_search(rng) {
-            no_impr = 0;
-        } else {
-            no_impr += 1;
-        }
-    }
-}
\ No newline at end of file
+/* Cada formiga tem seu próprio fluxo derivado da seed, então o resultado não depende da ordem de execução das threads */
+pub fn ant_rng(seed: u64, iteration: u16, ant: u16) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(((iteration as u64) << 16) | ant as u64);
+    rng
+}
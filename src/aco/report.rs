@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{aco::stop::StopReason, conformation::{Conformation, ConstructionStats}, pheromones::Pheromones, protein::Energy};
+
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub best_conformation: Conformation,
+    pub best: Energy,
+    /* Iteração em que `best` foi encontrado pela primeira vez */
+    pub best_iteration: Option<u16>,
+    pub iterations: Vec<IterationStats>,
+    /* Chamadas a `Conformation::eval` somadas de todas as formigas */
+    pub evaluations: u64,
+    pub stop_reason: StopReason,
+    pub elapsed: Duration,
+    /* Feromônios ao fim da execução, para salvar com `Pheromones::snapshot` */
+    pub pheromones: Pheromones
+}
+
+impl RunReport {
+    pub fn best_trace(&self) -> Vec<Energy> {
+        self.iterations.iter().map(|stats| stats.best_so_far).collect()
+    }
+
+    /// Erro relativo de `best` ao ótimo conhecido da configuração, se houver.
+    pub fn relative_error(&self) -> Option<f64> {
+        self.best_conformation.config().optimum.and_then(|optimum| self.best.relative_error_to_optimum(optimum))
+    }
+
+    /// Contadores da construção somados de todas as iterações.
+    pub fn construction_stats(&self) -> ConstructionStats {
+        self.iterations.iter().map(IterationStats::construction_stats).sum()
+    }
+
+    /* Iterações seguidas, ao fim da execução, sem melhorar `best` */
+    pub fn stagnation(&self) -> usize {
+        match self.best_iteration {
+            Some(best_iteration) => self.iterations.len() - 1 - best_iteration as usize,
+            None => self.iterations.len()
+        }
+    }
+}
+
+/// Estatísticas de uma iteração, com as energias (veja `Energy`) das formigas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IterationStats {
+    pub iteration: u16,
+    pub best_so_far: Energy,
+    /* Energias das formigas desta iteração */
+    pub best: Energy,
+    pub mean: Energy,
+    pub worst: Energy,
+    pub std_dev: f64,
+    /* Entropia normalizada dos feromônios após a atualização, em [0, 1] */
+    pub pheromone_entropy: f64,
+    /* Retrocessos da construção (veja `ConstructionStats`) */
+    pub rewinds: u32,
+    pub undone_steps: u32,
+    pub lookahead_rejections: u32,
+    pub local_search_improvements: u32,
+    pub elapsed_secs: f64
+}
+
+impl IterationStats {
+    /* `best_so_far` e `fitnesses` na convenção de `Conformation::eval` */
+    pub fn new(
+        iteration: u16,
+        best_so_far: f64,
+        fitnesses: &[f64],
+        construction: ConstructionStats,
+        local_search_improvements: u32,
+        pheromone_entropy: f64,
+        elapsed: Duration
+    ) -> Self {
+        let n = fitnesses.len().max(1) as f64;
+
+        let best = fitnesses.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let worst = fitnesses.iter().cloned().fold(f64::INFINITY, f64::min);
+        let mean = fitnesses.iter().sum::<f64>() / n;
+        let variance = fitnesses.iter().map(|fit| (fit - mean).powi(2)).sum::<f64>() / n;
+
+        Self {
+            iteration,
+            best_so_far: Energy::from_fitness(best_so_far),
+            best: Energy::from_fitness(best),
+            mean: Energy::from_fitness(mean),
+            worst: Energy::from_fitness(worst),
+            std_dev: variance.sqrt(),
+            pheromone_entropy,
+            rewinds: construction.backtracks,
+            undone_steps: construction.undone_steps,
+            lookahead_rejections: construction.lookahead_rejections,
+            local_search_improvements,
+            elapsed_secs: elapsed.as_secs_f64()
+        }
+    }
+
+    pub fn construction_stats(&self) -> ConstructionStats {
+        ConstructionStats {
+            backtracks: self.rewinds,
+            undone_steps: self.undone_steps,
+            lookahead_rejections: self.lookahead_rejections
+        }
+    }
+}
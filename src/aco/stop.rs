@@ -0,0 +1,130 @@
+use std::{fmt, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::protein::Energy;
+
+/// Critérios de parada além de `max_iter`. A execução para no primeiro que for atingido,
+/// sempre verificados ao fim de cada iteração.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct StopCriteria {
+    /* Energia alvo, normalmente o ótimo conhecido do benchmark */
+    pub target: Option<Energy>,
+    pub time_limit_secs: Option<f64>,
+    pub max_evaluations: Option<u64>,
+    /* Iterações seguidas sem melhorar a melhor solução */
+    pub max_stagnation: Option<u16>,
+    /* Entropia dos feromônios abaixo da qual a matriz é considerada convergida */
+    pub min_entropy: Option<f64>
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    MaxIterations,
+    TargetReached,
+    TimeLimit,
+    EvaluationLimit,
+    Stagnation,
+    Converged,
+    /* Interrompida por um `CancellationToken` */
+    Cancelled
+}
+
+/* Estado da execução consultado pelos critérios */
+#[derive(Debug, Clone, Copy)]
+pub struct SearchProgress {
+    pub best: Energy,
+    pub elapsed: Duration,
+    pub evaluations: u64,
+    pub stagnation: u16,
+    pub pheromone_entropy: f64
+}
+
+impl StopCriteria {
+    pub fn check(&self, progress: &SearchProgress) -> Option<StopReason> {
+        if self.target.is_some_and(|target| !target.is_better_than(progress.best)) {
+            Some(StopReason::TargetReached)
+        } else if self.time_limit_secs.is_some_and(|limit| progress.elapsed.as_secs_f64() >= limit) {
+            Some(StopReason::TimeLimit)
+        } else if self.max_evaluations.is_some_and(|limit| progress.evaluations >= limit) {
+            Some(StopReason::EvaluationLimit)
+        } else if self.max_stagnation.is_some_and(|limit| progress.stagnation >= limit) {
+            Some(StopReason::Stagnation)
+        } else if self.min_entropy.is_some_and(|limit| progress.pheromone_entropy <= limit) {
+            Some(StopReason::Converged)
+        } else {
+            None
+        }
+    }
+
+    /// Fração da busca já feita, pelo limite de iterações, tempo ou avaliações mais próximo de
+    /// ser atingido. Os outros critérios não dão para prever.
+    pub fn fraction(&self, iterations: u16, max_iter: u16, elapsed: Duration, evaluations: u64) -> f64 {
+        [
+            Some(iterations as f64 / max_iter.max(1) as f64),
+            self.time_limit_secs.map(|limit| elapsed.as_secs_f64() / limit),
+            self.max_evaluations.map(|limit| evaluations as f64 / limit.max(1) as f64)
+        ]
+        .into_iter()
+        .flatten()
+        .fold(0.0, f64::max)
+        .min(1.0)
+    }
+}
+
+/// Andamento de uma execução, enviado ao fim de cada iteração (veja `AcoEngine::with_progress`).
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    /* Iterações concluídas */
+    pub iteration: u16,
+    /* Em [0, 1]; 1 quando a busca terminou */
+    pub fraction: f64,
+    pub elapsed: Duration,
+    /* Tempo restante estimado pelo ritmo até agora */
+    pub eta: Option<Duration>,
+    pub best: Energy
+}
+
+impl Progress {
+    pub fn new(iteration: u16, fraction: f64, elapsed: Duration, best: Energy) -> Self {
+        let eta = (fraction > 0.0).then(|| elapsed.mul_f64((1.0 - fraction) / fraction));
+        Self { iteration, fraction, elapsed, eta, best }
+    }
+}
+
+/// Pedido de interrupção compartilhado entre quem executa a busca e quem quer pará-la, verificado
+/// entre uma formiga e outra. A busca interrompida termina com `StopReason::Cancelled` e a
+/// melhor conformação até então; a iteração em andamento é descartada.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            StopReason::MaxIterations => "max_iterations",
+            StopReason::TargetReached => "target_reached",
+            StopReason::TimeLimit => "time_limit",
+            StopReason::EvaluationLimit => "evaluation_limit",
+            StopReason::Stagnation => "stagnation",
+            StopReason::Converged => "converged",
+            StopReason::Cancelled => "cancelled"
+        };
+
+        write!(f, "{}", s)
+    }
+}
@@ -0,0 +1,381 @@
+use std::{path::{Path, PathBuf}, sync::Arc};
+
+use clap::{Parser, Subcommand};
+
+use ic_rust::{aco::{logger::{default::{DefaultLogger, LogLevel}, file::FileLogger}, stop::Progress}, aco_protein_folding_2dhp, cli::{exit_with_error, known_optimum, run_seed, ConfigArgs, EnergyArgs, FormatArg, LanguageArg, LoggerArg, ProteinArgs, StopArgs, SweepArgs}, export::{write_iterations_csv, ExportFormat, RunRecord}, protein::{load_benchmark_with, load_benchmarks_with, EnergyModel}, run_checkpointed, ACOConfig, AcoEngine, Checkpoint, PheromoneSnapshot, Protein, RunReport};
+
+#[derive(Debug, Parser)]
+#[command(name = "benchmark", version, about = "ACO para o dobramento de proteínas no modelo HP, em redes 2D e 3D")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Executa o ACO uma vez e imprime a melhor conformação encontrada
+    Fold {
+        #[command(flatten)]
+        protein: ProteinArgs,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+
+        /// Nível de log no terminal
+        #[arg(long, value_enum, default_value_t = LoggerArg::None)]
+        logger: LoggerArg,
+
+        /// Idioma do log no terminal
+        #[arg(long, value_enum, default_value_t = LanguageArg::Pt)]
+        log_language: LanguageArg,
+
+        /// Arquivo JSON Lines onde cada evento da busca é gravado
+        #[arg(long)]
+        event_log: Option<PathBuf>,
+
+        /// Grava também os feromônios a cada iteração no log de eventos
+        #[arg(long, requires = "event_log")]
+        event_log_pheromones: bool,
+
+        /// Arquivo onde o resultado é anexado
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = FormatArg::Jsonl)]
+        format: FormatArg,
+
+        /// Arquivo CSV com as estatísticas de cada iteração
+        #[arg(long)]
+        trace: Option<PathBuf>,
+
+        /// Continua a partir dos feromônios e da melhor conformação salvos com --save-pheromones
+        #[arg(long)]
+        warm_start: Option<PathBuf>,
+
+        /// Arquivo onde os feromônios finais e a melhor conformação são salvos
+        #[arg(long)]
+        save_pheromones: Option<PathBuf>,
+
+        /// Arquivo onde o estado da execução é salvo periodicamente, para continuar com o comando resume
+        #[arg(long)]
+        checkpoint: Option<PathBuf>,
+
+        /// Iterações entre um checkpoint e outro
+        #[arg(long, default_value_t = 10, requires = "checkpoint")]
+        checkpoint_every: u16,
+
+        /// Mostra o andamento e o tempo restante estimado no stderr
+        #[arg(long)]
+        progress: bool
+    },
+
+    /// Continua uma execução do comando fold a partir do checkpoint, com a mesma configuração
+    Resume {
+        /// Arquivo salvo com --checkpoint, atualizado ao longo da execução
+        checkpoint: PathBuf,
+
+        /// Arquivo com a matriz de interação da execução, se ela não usou um modelo embutido
+        #[arg(long)]
+        energy_matrix: Option<PathBuf>,
+
+        /// Nível de log no terminal
+        #[arg(long, value_enum, default_value_t = LoggerArg::None)]
+        logger: LoggerArg,
+
+        /// Idioma do log no terminal
+        #[arg(long, value_enum, default_value_t = LanguageArg::Pt)]
+        log_language: LanguageArg,
+
+        /// Arquivo JSON Lines onde cada evento da busca é gravado
+        #[arg(long)]
+        event_log: Option<PathBuf>,
+
+        /// Grava também os feromônios a cada iteração no log de eventos
+        #[arg(long, requires = "event_log")]
+        event_log_pheromones: bool,
+
+        /// Arquivo onde o resultado é anexado
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = FormatArg::Jsonl)]
+        format: FormatArg,
+
+        /// Arquivo CSV com as estatísticas de cada iteração
+        #[arg(long)]
+        trace: Option<PathBuf>,
+
+        /// Arquivo onde os feromônios finais e a melhor conformação são salvos
+        #[arg(long)]
+        save_pheromones: Option<PathBuf>,
+
+        /// Mostra o andamento e o tempo restante estimado no stderr
+        #[arg(long)]
+        progress: bool
+    },
+
+    /// Executa os benchmarks selecionados com uma única configuração
+    Benchmark {
+        /// Índices dos benchmarks (padrão: todos)
+        #[arg(long = "index", short, value_delimiter = ',')]
+        indices: Vec<usize>,
+
+        /// Quantidade de execuções por benchmark
+        #[arg(long, default_value_t = 3)]
+        runs: u16,
+
+        #[arg(long, default_value = "benchmarks.txt")]
+        benchmarks_file: PathBuf,
+
+        #[command(flatten)]
+        energy: EnergyArgs,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+
+        /// Arquivo onde os resultados são anexados
+        #[arg(long, short, default_value = "benchmark_results.jsonl")]
+        output: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = FormatArg::Jsonl)]
+        format: FormatArg
+    },
+
+    /// Executa os benchmarks para todas as combinações de parâmetros
+    Sweep {
+        /// Índices dos benchmarks (padrão: todos)
+        #[arg(long = "index", short, value_delimiter = ',')]
+        indices: Vec<usize>,
+
+        /// Quantidade de execuções por benchmark e configuração
+        #[arg(long, default_value_t = 3)]
+        runs: u16,
+
+        /// Quantidade de vezes que a varredura inteira é repetida
+        #[arg(long, default_value_t = 1)]
+        repeat: u32,
+
+        #[arg(long, default_value = "benchmarks.txt")]
+        benchmarks_file: PathBuf,
+
+        #[command(flatten)]
+        energy: EnergyArgs,
+
+        #[command(flatten)]
+        grid: Box<SweepArgs>,
+
+        /// Arquivo onde os resultados são anexados
+        #[arg(long, short, default_value = "benchmark_results.jsonl")]
+        output: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = FormatArg::Jsonl)]
+        format: FormatArg
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Fold { protein, config, logger, log_language, event_log, event_log_pheromones, output, format, trace, warm_start, save_pheromones, checkpoint, checkpoint_every, progress } => {
+            let (protein, optimum) = protein.load();
+            let stop = config.stop;
+            let config: ACOConfig = config.into();
+            let optimum = known_optimum(optimum, &protein, &config);
+            let config = ACOConfig { stop: stop.criteria(optimum), optimum, ..config };
+
+            let engine = match warm_start {
+                Some(path) => {
+                    let start = PheromoneSnapshot::load(&path)
+                        .and_then(|snapshot| snapshot.warm_start(&protein, config))
+                        .unwrap_or_else(exit_with_error);
+                    AcoEngine::with_start(&protein, config, start)
+                }
+                None => AcoEngine::new(&protein, config)
+            };
+
+            let checkpoint = checkpoint.as_deref().map(|path| (path, checkpoint_every));
+            let logger = DefaultLogger::new(logger.into()).with_labels(log_language.into());
+            let event_log = event_log_file(event_log, event_log_pheromones);
+            let report = run_engine(engine, logger, event_log, checkpoint, progress);
+
+            print_fold(&protein, config, &report, FoldOutput { output, format, trace, save_pheromones });
+        }
+        Command::Resume { checkpoint, energy_matrix, logger, log_language, event_log, event_log_pheromones, output, format, trace, save_pheromones, progress } => {
+            let saved = Checkpoint::load(&checkpoint).unwrap_or_else(exit_with_error);
+            /* O checkpoint guarda só o nome do modelo, que basta para os embutidos */
+            let protein = match energy_matrix {
+                Some(path) => saved.protein_with(&Arc::new(EnergyModel::load(&path).unwrap_or_else(exit_with_error))),
+                None => saved.protein()
+            }.unwrap_or_else(exit_with_error);
+            let config = saved.config();
+            let every = saved.every;
+
+            println!("Continuando da iteração {}", saved.iteration());
+
+            let engine = saved.engine(&protein).unwrap_or_else(exit_with_error);
+            let logger = DefaultLogger::new(logger.into()).with_labels(log_language.into());
+            let event_log = event_log_file(event_log, event_log_pheromones);
+            let report = run_engine(engine, logger, event_log, Some((&checkpoint, every)), progress);
+
+            print_fold(&protein, config, &report, FoldOutput { output, format, trace, save_pheromones });
+        }
+        Command::Benchmark { indices, runs, benchmarks_file, energy, config, output, format } => {
+            let model = energy.model();
+            let indices = benchmark_indices(indices, &benchmarks_file, &model);
+            let base_seed = config.seed;
+            let stop = config.stop;
+            let config: ACOConfig = config.into();
+
+            println!("{:?}", config);
+
+            let mut run = 0;
+            for i in indices {
+                for _ in 0..runs {
+                    let config = ACOConfig { seed: run_seed(base_seed, run), ..config };
+                    run_benchmark(i, config, stop, &benchmarks_file, &model, &output, format.into());
+                    run += 1;
+                }
+            }
+        }
+        Command::Sweep { indices, runs, repeat, benchmarks_file, energy, grid, output, format } => {
+            let model = energy.model();
+            let indices = benchmark_indices(indices, &benchmarks_file, &model);
+            let configs = grid.configs();
+
+            let mut run = 0;
+            for _ in 0..repeat {
+                for &config in &configs {
+                    println!("{:?}", config);
+
+                    for &i in &indices {
+                        for _ in 0..runs {
+                            let config = ACOConfig { seed: run_seed(grid.seed, run), ..config };
+                            run_benchmark(i, config, grid.stop, &benchmarks_file, &model, &output, format.into());
+                            run += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/* Executa `engine` até o fim, gravando checkpoints em `(arquivo, intervalo)` se pedido */
+fn run_engine(
+    engine: AcoEngine,
+    logger: DefaultLogger,
+    event_log: Option<FileLogger>,
+    checkpoint: Option<(&Path, u16)>,
+    progress: bool
+) -> RunReport {
+    let engine = if progress { engine.with_progress(print_progress) } else { engine };
+    let mut logger = (logger, event_log);
+
+    let report = match checkpoint {
+        Some((path, every)) => run_checkpointed(engine, &mut logger, path, every).unwrap_or_else(exit_with_error),
+        None => engine.run(&mut logger)
+    };
+
+    if progress {
+        eprintln!();
+    }
+
+    if let Some(event_log) = logger.1 {
+        event_log.finish().unwrap_or_else(exit_with_error);
+    }
+
+    report
+}
+
+fn event_log_file(path: Option<PathBuf>, pheromones: bool) -> Option<FileLogger> {
+    let logger = FileLogger::create(&path?).unwrap_or_else(exit_with_error);
+    Some(logger.with_pheromones(pheromones))
+}
+
+fn print_progress(progress: Progress) {
+    let eta = progress.eta.map_or("?".to_string(), |eta| format!("{:.0?}", eta));
+    eprint!(
+        "\r{:>5.1}%  |  Iteração {}  |  Melhor energia: {}  |  Restante: {}    ",
+        progress.fraction * 100.0,
+        progress.iteration,
+        progress.best,
+        eta
+    );
+}
+
+/* Arquivos opcionais gravados ao fim de `fold` e `resume` */
+struct FoldOutput {
+    output: Option<PathBuf>,
+    format: FormatArg,
+    trace: Option<PathBuf>,
+    save_pheromones: Option<PathBuf>
+}
+
+fn print_fold(protein: &Protein, config: ACOConfig, report: &RunReport, files: FoldOutput) {
+    println!("Seed: {}", config.seed);
+    println!("Tempo: {:?}", report.elapsed);
+    if report.best.is_finite() {
+        println!("Conformação: {}", report.best_conformation);
+        println!("Energia: {}", report.best);
+    } else {
+        println!("Conformação: nenhuma, a busca não completou nenhuma iteração");
+    }
+    if let (Some(optimum), Some(error)) = (config.optimum, report.relative_error()) {
+        println!("Ótimo: {} (erro relativo: {:.1}%)", optimum, error * 100.0);
+    }
+    println!("Parada: {} (iteração {})", report.stop_reason, report.iterations.len());
+
+    if let Some(output) = files.output {
+        append_record(&output, files.format.into(), RunRecord::new(protein, config, report));
+    }
+
+    if let Some(trace) = files.trace {
+        write_iterations_csv(&trace, &report.iterations).unwrap_or_else(exit_with_error);
+    }
+
+    if let Some(path) = files.save_pheromones {
+        report.pheromones.snapshot(protein, report.best.is_finite().then_some(&report.best_conformation))
+            .save(&path)
+            .unwrap_or_else(exit_with_error);
+    }
+}
+
+fn run_benchmark(
+    i: usize,
+    config: ACOConfig,
+    stop: StopArgs,
+    benchmarks_file: &Path,
+    model: &Arc<EnergyModel>,
+    out_file: &Path,
+    format: ExportFormat
+) {
+    let benchmark = load_benchmark_with(benchmarks_file, i, model).unwrap_or_else(exit_with_error);
+    let optimum = known_optimum(benchmark.optimum, &benchmark.protein, &config);
+    let config = ACOConfig { stop: stop.criteria(optimum), optimum, ..config };
+
+    let report = aco_protein_folding_2dhp(&benchmark.protein, config, DefaultLogger::new(LogLevel::None));
+
+    let record = RunRecord::new(&benchmark.protein, config, &report)
+        .with_benchmark(benchmark.name, i);
+
+    append_record(out_file, format, record);
+}
+
+/* Uma execução sem nenhuma iteração não tem resultado: a energia infinita nem cabe em JSON */
+fn append_record(path: &Path, format: ExportFormat, record: RunRecord) {
+    if !record.energy.is_finite() {
+        eprintln!("Nenhuma iteração completa, nada gravado em {}", path.display());
+        return;
+    }
+
+    format.append(path, &record).unwrap_or_else(exit_with_error);
+}
+
+fn benchmark_indices(indices: Vec<usize>, benchmarks_file: &Path, model: &Arc<EnergyModel>) -> Vec<usize> {
+    if indices.is_empty() {
+        (0..load_benchmarks_with(benchmarks_file, model).unwrap_or_else(exit_with_error).len()).collect()
+    } else {
+        indices
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use macroquad::prelude::*;
+
+use ic_rust::{aco::logger::{macroquad::MacroquadLogger, replay::Replay, AsyncACOLogger, Event}, cli::{exit_with_error, known_optimum, ConfigArgs, EnergyArgs, ProteinArgs, VisualLoggerArg}, ACOConfig, AcoEngine, CancellationToken, Protein};
+
+#[derive(Debug, Parser)]
+#[command(name = "visualizer", version, about = "Executa o ACO com a visualização em macroquad")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    protein: ProteinArgs,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    /// Eventos que disparam um novo quadro
+    #[arg(long, value_enum, default_value_t = VisualLoggerArg::Change)]
+    logger: VisualLoggerArg
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Reproduz uma execução gravada com --event-log do benchmark
+    Replay {
+        /// Log de eventos em JSON Lines
+        event_log: PathBuf,
+
+        #[command(flatten)]
+        energy: EnergyArgs
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Some(Command::Replay { event_log, energy }) = cli.command {
+        let replay = Replay::load(&event_log, &energy.model()).unwrap_or_else(exit_with_error);
+        if replay.frames.is_empty() {
+            exit_with_error::<_, ()>("o log de eventos não tem conformações para mostrar");
+        }
+
+        macroquad::Window::from_config(window_conf(), play(replay));
+        return;
+    }
+
+    let (protein, optimum) = cli.protein.load();
+    let config: ACOConfig = cli.config.into();
+    let optimum = known_optimum(optimum, &protein, &config);
+    let config = ACOConfig { stop: cli.config.stop.criteria(optimum), optimum, ..config };
+
+    macroquad::Window::from_config(window_conf(), visualize(protein, config, cli.logger.into()));
+}
+
+async fn visualize(protein: Protein, config: ACOConfig, logger: MacroquadLogger) {
+    loop {
+        let cancel = CancellationToken::new();
+        let engine = AcoEngine::new(&protein, config).with_cancellation(cancel.clone());
+
+        let report = engine.run_async(EscapeCancels { logger: logger.clone(), cancel: &cancel }).await;
+
+        println!("Tempo: {:?}", report.elapsed);
+        println!("Parada: {} (iteração {})", report.stop_reason, report.iterations.len());
+
+        /* Enter executa de novo e Esc fecha */
+        loop {
+            report.best_conformation.draw(config.max_iter, report.best, None).await;
+
+            if is_key_pressed(KeyCode::Enter) {
+                break;
+            }
+            if is_key_pressed(KeyCode::Escape) {
+                return;
+            }
+        }
+    }
+}
+
+/* Desenha como o `MacroquadLogger` e cancela a busca quando Esc é pressionado em algum quadro */
+struct EscapeCancels<'a> {
+    logger: MacroquadLogger,
+    cancel: &'a CancellationToken
+}
+
+impl AsyncACOLogger for EscapeCancels<'_> {
+    async fn log_async(&mut self, event: &Event<'_>) {
+        self.logger.log_async(event).await;
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.cancel.cancel();
+        }
+    }
+}
+
+/* Quadros por segundo na reprodução: o padrão e os limites de ↑/↓ */
+const REPLAY_SPEED: f32 = 30.0;
+const REPLAY_SPEED_RANGE: (f32, f32) = (0.5, 3840.0);
+
+/* Barra de tempo no topo, onde o clique leva ao quadro */
+const TIMELINE_Y: f32 = 100.0;
+
+/* Espaço pausa, as setas andam um quadro e mudam a velocidade, Home/End vão ao começo e ao fim,
+   N vai à próxima melhora da melhor energia, B à iteração em que a melhor foi encontrada */
+async fn play(replay: Replay) {
+    let last = replay.frames.len() - 1;
+    /* Fracionário, para velocidades menores que um quadro por tela */
+    let mut position = 0.0_f32;
+    let mut speed = REPLAY_SPEED;
+    let mut paused = false;
+
+    loop {
+        let current = position as usize;
+
+        if is_key_pressed(KeyCode::Escape) {
+            return;
+        }
+        if is_key_pressed(KeyCode::Space) {
+            paused = !paused;
+        }
+        if is_key_pressed(KeyCode::Up) {
+            speed = (speed * 2.0).min(REPLAY_SPEED_RANGE.1);
+        }
+        if is_key_pressed(KeyCode::Down) {
+            speed = (speed / 2.0).max(REPLAY_SPEED_RANGE.0);
+        }
+        if is_key_pressed(KeyCode::Right) {
+            paused = true;
+            position = (current + 1).min(last) as f32;
+        }
+        if is_key_pressed(KeyCode::Left) {
+            paused = true;
+            position = current.saturating_sub(1) as f32;
+        }
+        if is_key_pressed(KeyCode::Home) {
+            position = 0.0;
+        }
+        if is_key_pressed(KeyCode::End) {
+            position = last as f32;
+        }
+        if is_key_pressed(KeyCode::N) && let Some(i) = replay.next_improvement(current) {
+            position = i as f32;
+        }
+        if is_key_pressed(KeyCode::B) && let Some(i) = replay.best_improvement() {
+            position = i as f32;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        if is_mouse_button_down(MouseButton::Left) && (mouse_y - TIMELINE_Y).abs() < 10.0 {
+            position = (((mouse_x - 10.0) / (screen_width() - 20.0)).clamp(0.0, 1.0) * last as f32).round();
+        } else if !paused {
+            position = (position + get_frame_time() * speed).min(last as f32);
+        }
+
+        let frame = &replay.frames[position as usize];
+        frame.conformation.draw_frame(frame.iteration, frame.best, replay.pheromones(frame));
+        draw_timeline(&replay, position as usize);
+
+        let ant = frame.ant.map_or(String::new(), |ant| format!("  |  Formiga {}", ant));
+        let state = if paused { "  |  Pausado" } else { "" };
+        let status = format!("Quadro {}/{}{}  |  {} quadros/s{}", position as usize + 1, last + 1, ant, speed, state);
+        draw_text(status, 10.0, 70.0, 20.0, BLACK);
+
+        next_frame().await;
+    }
+}
+
+/* Marca em verde os quadros em que a melhor energia melhorou */
+fn draw_timeline(replay: &Replay, current: usize) {
+    let width = screen_width() - 20.0;
+    let x = |i: usize| 10.0 + width * i as f32 / (replay.frames.len() - 1).max(1) as f32;
+
+    draw_line(10.0, TIMELINE_Y, 10.0 + width, TIMELINE_Y, 4.0, LIGHTGRAY);
+
+    for (i, _) in replay.frames.iter().enumerate().filter(|(_, frame)| frame.improved) {
+        draw_line(x(i), TIMELINE_Y - 6.0, x(i), TIMELINE_Y + 6.0, 2.0, DARKGREEN);
+    }
+
+    draw_circle(x(current), TIMELINE_Y, 6.0, RED);
+}
+
+fn window_conf() -> Conf {
+    Conf {
+        window_title: "Protein Folding 2DHP".to_string(),
+        window_width: 600,
+        window_height: 600,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,541 @@
+use std::{fmt::Display, path::PathBuf, process, sync::Arc};
+
+use clap::{Args, ValueEnum};
+
+use crate::{aco::{config::ACOConfig, logger::default::{Labels, LogLevel}, stop::StopCriteria}, conformation::{backtracking::DEFAULT_BACKTRACK_DEPTH, lattice::LatticeKind, local_search::Neighbourhood, Backtracking, Construction, Lookahead}, export::ExportFormat, pheromones::{PheromoneUpdate, UpdateParams}, protein::{load_benchmark_with, parse_protein_sequence_with, Energy, EnergyModel, Protein}};
+
+#[cfg(feature = "visual")]
+use crate::aco::logger::macroquad::{DrawOn, MacroquadLogger};
+
+#[derive(Debug, Args)]
+pub struct ProteinArgs {
+    /// Sequência HP (ex.: HPHPPHHPHPPHPHHPPHPH)
+    #[arg(long, short, conflicts_with = "benchmark", required_unless_present = "benchmark")]
+    pub sequence: Option<String>,
+
+    /// Índice da linha em benchmarks.txt
+    #[arg(long, short)]
+    pub benchmark: Option<usize>,
+
+    #[arg(long, default_value = "benchmarks.txt")]
+    pub benchmarks_file: PathBuf,
+
+    #[command(flatten)]
+    pub energy: EnergyArgs
+}
+
+impl ProteinArgs {
+    /* Retorna a proteína e, se vier de um benchmark, a melhor energia conhecida */
+    pub fn load(&self) -> (Protein, Option<Energy>) {
+        let model = self.energy.model();
+
+        match (&self.sequence, self.benchmark) {
+            (Some(sequence), _) => (parse_protein_sequence_with(sequence, &model).unwrap_or_else(exit_with_error), None),
+            (None, Some(i)) => {
+                let benchmark = load_benchmark_with(&self.benchmarks_file, i, &model).unwrap_or_else(exit_with_error);
+                (benchmark.protein, benchmark.optimum)
+            }
+            (None, None) => unreachable!("clap exige --sequence ou --benchmark")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct EnergyArgs {
+    /// Modelo de energia
+    #[arg(long, value_enum, default_value_t = EnergyArg::Hp)]
+    pub energy: EnergyArg,
+
+    /// Arquivo com uma matriz de interação (ex.: Miyazawa–Jernigan), no lugar de --energy
+    #[arg(long, conflicts_with = "energy")]
+    pub energy_matrix: Option<PathBuf>
+}
+
+impl EnergyArgs {
+    pub fn model(&self) -> Arc<EnergyModel> {
+        match &self.energy_matrix {
+            Some(path) => Arc::new(EnergyModel::load(path).unwrap_or_else(exit_with_error)),
+            None => match self.energy {
+                EnergyArg::Hp => EnergyModel::hp(),
+                EnergyArg::Hpnx => EnergyModel::hpnx()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct ConfigArgs {
+    /* Sem formigas não há conformação nenhuma para atualizar os feromônios */
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u16).range(1..))]
+    pub ant_count: u16,
+
+    #[arg(long, default_value_t = 25)]
+    pub max_iter: u16,
+
+    #[arg(long, default_value_t = 20)]
+    pub no_impr_max: u16,
+
+    #[arg(long, default_value_t = 0.9)]
+    pub evaporation: f64,
+
+    #[arg(long, default_value_t = 1.0)]
+    pub alpha: f64,
+
+    #[arg(long, default_value_t = 2.0)]
+    pub beta: f64,
+
+    #[arg(long, default_value_t = 0.5)]
+    pub neutral_mutation_rate: f64,
+
+    /// Seed do gerador aleatório (padrão: aleatória)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Vizinhança da busca local
+    #[arg(long, value_enum, default_value_t = NeighbourhoodArg::Pivot)]
+    pub neighbourhood: NeighbourhoodArg,
+
+    /// Rede onde a proteína é dobrada
+    #[arg(long, value_enum, default_value_t = LatticeArg::Square)]
+    pub lattice: LatticeArg,
+
+    /// Regra de atualização dos feromônios
+    #[arg(long, value_enum, default_value_t = PheromoneUpdateArg::AntSystem)]
+    pub pheromone_update: PheromoneUpdateArg,
+
+    #[command(flatten)]
+    pub update: UpdateArgs,
+
+    /// Como as formigas constroem a conformação
+    #[arg(long, value_enum, default_value_t = ConstructionArg::Forward)]
+    pub construction: ConstructionArg,
+
+    /// O que a formiga faz quando fica sem saída
+    #[arg(long, value_enum, default_value_t = BacktrackingArg::Halving)]
+    pub backtracking: BacktrackingArg,
+
+    /// Passos desfeitos um a um antes de desfazer metade da cadeia, com `--backtracking limited-depth`
+    #[arg(long, default_value_t = DEFAULT_BACKTRACK_DEPTH)]
+    pub backtrack_depth: u16,
+
+    /// Como a formiga descarta posições que prendem a cadeia
+    #[arg(long, value_enum, default_value_t = LookaheadArg::Neighbour)]
+    pub lookahead: LookaheadArg,
+
+    #[command(flatten)]
+    pub stop: StopArgs
+}
+
+impl From<ConfigArgs> for ACOConfig {
+    fn from(args: ConfigArgs) -> Self {
+        ACOConfig {
+            ant_count: args.ant_count,
+            max_iter: args.max_iter,
+            no_impr_max: args.no_impr_max,
+            evaporation: args.evaporation,
+            alpha: args.alpha,
+            beta: args.beta,
+            neutral_mutation_rate: args.neutral_mutation_rate,
+            seed: args.seed.unwrap_or_else(rand::random),
+            stop: args.stop.criteria(None),
+            neighbourhood: args.neighbourhood.into(),
+            lattice: args.lattice.into(),
+            pheromone_update: args.pheromone_update.into(),
+            update_params: args.update.into(),
+            construction: args.construction.into(),
+            backtracking: args.backtracking.into(),
+            backtrack_depth: args.backtrack_depth,
+            lookahead: args.lookahead.into(),
+            optimum: None
+        }
+    }
+}
+
+/* Cada parâmetro aceita uma lista separada por vírgulas */
+#[derive(Debug, Args)]
+pub struct SweepArgs {
+    #[arg(long, value_delimiter = ',', default_values_t = [10, 20], value_parser = clap::value_parser!(u16).range(1..))]
+    pub ant_count: Vec<u16>,
+
+    #[arg(long, default_value_t = 60)]
+    pub max_iter: u16,
+
+    #[arg(long, value_delimiter = ',', default_values_t = [10, 20])]
+    pub no_impr_max: Vec<u16>,
+
+    #[arg(long, value_delimiter = ',', default_values_t = [0.5, 0.7, 0.9])]
+    pub evaporation: Vec<f64>,
+
+    #[arg(long, value_delimiter = ',', default_values_t = [1.0, 2.0, 3.0])]
+    pub alpha: Vec<f64>,
+
+    #[arg(long, value_delimiter = ',', default_values_t = [1.0, 2.0, 3.0])]
+    pub beta: Vec<f64>,
+
+    #[arg(long, value_delimiter = ',', default_values_t = [0.0, 0.5])]
+    pub neutral_mutation_rate: Vec<f64>,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [NeighbourhoodArg::Pivot])]
+    pub neighbourhood: Vec<NeighbourhoodArg>,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [LatticeArg::Square])]
+    pub lattice: Vec<LatticeArg>,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [PheromoneUpdateArg::AntSystem])]
+    pub pheromone_update: Vec<PheromoneUpdateArg>,
+
+    #[command(flatten)]
+    pub update: UpdateArgs,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [ConstructionArg::Forward])]
+    pub construction: Vec<ConstructionArg>,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [BacktrackingArg::Halving])]
+    pub backtracking: Vec<BacktrackingArg>,
+
+    #[arg(long, default_value_t = DEFAULT_BACKTRACK_DEPTH)]
+    pub backtrack_depth: u16,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [LookaheadArg::Neighbour])]
+    pub lookahead: Vec<LookaheadArg>,
+
+    /// Seed da primeira execução; as seguintes usam seeds consecutivas (padrão: aleatória)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub stop: StopArgs
+}
+
+impl SweepArgs {
+    /* Produto cartesiano das listas, na ordem em que os parâmetros aparecem */
+    pub fn configs(&self) -> Vec<ACOConfig> {
+        let base = ACOConfig {
+            ant_count: 0,
+            max_iter: self.max_iter,
+            no_impr_max: 0,
+            evaporation: 0.0,
+            alpha: 0.0,
+            beta: 0.0,
+            neutral_mutation_rate: 0.0,
+            seed: 0,
+            stop: self.stop.criteria(None),
+            neighbourhood: Neighbourhood::default(),
+            lattice: LatticeKind::default(),
+            pheromone_update: PheromoneUpdate::default(),
+            update_params: self.update.into(),
+            construction: Construction::default(),
+            backtracking: Backtracking::default(),
+            backtrack_depth: self.backtrack_depth,
+            lookahead: Lookahead::default(),
+            optimum: None
+        };
+
+        let configs = vec![base];
+        let configs = expand(configs, &self.ant_count, |c, v| c.ant_count = v);
+        let configs = expand(configs, &self.no_impr_max, |c, v| c.no_impr_max = v);
+        let configs = expand(configs, &self.evaporation, |c, v| c.evaporation = v);
+        let configs = expand(configs, &self.alpha, |c, v| c.alpha = v);
+        let configs = expand(configs, &self.beta, |c, v| c.beta = v);
+        let configs = expand(configs, &self.neutral_mutation_rate, |c, v| c.neutral_mutation_rate = v);
+        let configs = expand(configs, &self.neighbourhood, |c, v| c.neighbourhood = v.into());
+        let configs = expand(configs, &self.lattice, |c, v| c.lattice = v.into());
+        let configs = expand(configs, &self.pheromone_update, |c, v| c.pheromone_update = v.into());
+        let configs = expand(configs, &self.construction, |c, v| c.construction = v.into());
+        let configs = expand(configs, &self.backtracking, |c, v| c.backtracking = v.into());
+        expand(configs, &self.lookahead, |c, v| c.lookahead = v.into())
+    }
+}
+
+fn expand<T: Copy>(configs: Vec<ACOConfig>, values: &[T], set: impl Fn(&mut ACOConfig, T)) -> Vec<ACOConfig> {
+    let mut expanded = Vec::with_capacity(configs.len() * values.len());
+
+    for config in configs {
+        for &v in values {
+            let mut config = config;
+            set(&mut config, v);
+            expanded.push(config);
+        }
+    }
+
+    expanded
+}
+
+/* Parâmetros das regras de atualização dos feromônios; cada regra só usa os seus */
+#[derive(Debug, Clone, Copy, Args)]
+pub struct UpdateArgs {
+    /// Peso do depósito da melhor solução (elitist)
+    #[arg(long, default_value_t = UpdateParams::default().elitist_weight)]
+    pub elitist_weight: f64,
+
+    /// Quantidade de depósitos, contando o da melhor solução (rank-based)
+    #[arg(long, default_value_t = UpdateParams::default().rank_count)]
+    pub rank_count: u16,
+
+    /// Probabilidade que define τmin (max-min)
+    #[arg(long, default_value_t = UpdateParams::default().p_best)]
+    pub p_best: f64,
+
+    /// Iterações sem melhora até reiniciar os feromônios (max-min)
+    #[arg(long, default_value_t = UpdateParams::default().reinit_stagnation)]
+    pub reinit_stagnation: u16,
+
+    /// Evaporação da atualização local, no caminho de cada formiga ao terminar (ant-colony-system, em que as formigas executam uma de cada vez)
+    #[arg(long, default_value_t = UpdateParams::default().local_evaporation)]
+    pub local_evaporation: f64,
+
+    /// Probabilidade de seguir a direção de maior peso (ant-colony-system)
+    #[arg(long, default_value_t = UpdateParams::default().q0)]
+    pub q0: f64
+}
+
+impl From<UpdateArgs> for UpdateParams {
+    fn from(args: UpdateArgs) -> Self {
+        UpdateParams {
+            elitist_weight: args.elitist_weight,
+            rank_count: args.rank_count,
+            p_best: args.p_best,
+            reinit_stagnation: args.reinit_stagnation,
+            local_evaporation: args.local_evaporation,
+            q0: args.q0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct StopArgs {
+    /// Para ao atingir esta energia (ex.: -9)
+    #[arg(long, allow_negative_numbers = true)]
+    pub target: Option<f64>,
+
+    /// Para ao atingir o ótimo conhecido do benchmark
+    #[arg(long, conflicts_with = "target")]
+    pub stop_at_optimum: bool,
+
+    /// Tempo máximo em segundos
+    #[arg(long)]
+    pub time_limit: Option<f64>,
+
+    /// Quantidade máxima de avaliações de energia
+    #[arg(long)]
+    pub max_evaluations: Option<u64>,
+
+    /// Iterações seguidas sem melhora
+    #[arg(long)]
+    pub max_stagnation: Option<u16>,
+
+    /// Entropia dos feromônios em que a matriz é considerada convergida
+    #[arg(long)]
+    pub min_entropy: Option<f64>
+}
+
+impl StopArgs {
+    /* `optimum` é o ótimo conhecido da proteína, usado com --stop-at-optimum */
+    pub fn criteria(&self, optimum: Option<Energy>) -> StopCriteria {
+        let target = match (self.stop_at_optimum, optimum) {
+            (true, Some(optimum)) => Some(optimum),
+            _ => self.target.map(Energy::new)
+        };
+
+        StopCriteria {
+            target,
+            time_limit_secs: self.time_limit,
+            max_evaluations: self.max_evaluations,
+            max_stagnation: self.max_stagnation,
+            min_entropy: self.min_entropy
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LoggerArg {
+    Iteration,
+    Ant,
+    None
+}
+
+impl From<LoggerArg> for LogLevel {
+    fn from(arg: LoggerArg) -> Self {
+        match arg {
+            LoggerArg::Iteration => LogLevel::Iteration,
+            LoggerArg::Ant => LogLevel::Ant,
+            LoggerArg::None => LogLevel::None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LanguageArg {
+    Pt,
+    En
+}
+
+impl From<LanguageArg> for Labels {
+    fn from(arg: LanguageArg) -> Self {
+        match arg {
+            LanguageArg::Pt => Labels::PORTUGUESE,
+            LanguageArg::En => Labels::ENGLISH
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NeighbourhoodArg {
+    Pivot,
+    PullMove,
+    Hybrid
+}
+
+impl From<NeighbourhoodArg> for Neighbourhood {
+    fn from(arg: NeighbourhoodArg) -> Self {
+        match arg {
+            NeighbourhoodArg::Pivot => Neighbourhood::Pivot,
+            NeighbourhoodArg::PullMove => Neighbourhood::PullMove,
+            NeighbourhoodArg::Hybrid => Neighbourhood::Hybrid
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LatticeArg {
+    Square,
+    Triangular,
+    Cubic,
+    Fcc
+}
+
+impl From<LatticeArg> for LatticeKind {
+    fn from(arg: LatticeArg) -> Self {
+        match arg {
+            LatticeArg::Square => LatticeKind::Square,
+            LatticeArg::Triangular => LatticeKind::Triangular,
+            LatticeArg::Cubic => LatticeKind::Cubic,
+            LatticeArg::Fcc => LatticeKind::Fcc
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PheromoneUpdateArg {
+    AntSystem,
+    Elitist,
+    RankBased,
+    MaxMin,
+    AntColonySystem
+}
+
+impl From<PheromoneUpdateArg> for PheromoneUpdate {
+    fn from(arg: PheromoneUpdateArg) -> Self {
+        match arg {
+            PheromoneUpdateArg::AntSystem => PheromoneUpdate::AntSystem,
+            PheromoneUpdateArg::Elitist => PheromoneUpdate::Elitist,
+            PheromoneUpdateArg::RankBased => PheromoneUpdate::RankBased,
+            PheromoneUpdateArg::MaxMin => PheromoneUpdate::MaxMin,
+            PheromoneUpdateArg::AntColonySystem => PheromoneUpdate::AntColonySystem
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConstructionArg {
+    Forward,
+    Bidirectional
+}
+
+impl From<ConstructionArg> for Construction {
+    fn from(arg: ConstructionArg) -> Self {
+        match arg {
+            ConstructionArg::Forward => Construction::Forward,
+            ConstructionArg::Bidirectional => Construction::Bidirectional
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BacktrackingArg {
+    Halving,
+    Chronological,
+    LimitedDepth
+}
+
+impl From<BacktrackingArg> for Backtracking {
+    fn from(arg: BacktrackingArg) -> Self {
+        match arg {
+            BacktrackingArg::Halving => Backtracking::Halving,
+            BacktrackingArg::Chronological => Backtracking::Chronological,
+            BacktrackingArg::LimitedDepth => Backtracking::LimitedDepth
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LookaheadArg {
+    Neighbour,
+    FloodFill
+}
+
+impl From<LookaheadArg> for Lookahead {
+    fn from(arg: LookaheadArg) -> Self {
+        match arg {
+            LookaheadArg::Neighbour => Lookahead::Neighbour,
+            LookaheadArg::FloodFill => Lookahead::FloodFill
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EnergyArg {
+    Hp,
+    Hpnx
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FormatArg {
+    Jsonl,
+    Csv
+}
+
+impl From<FormatArg> for ExportFormat {
+    fn from(arg: FormatArg) -> Self {
+        match arg {
+            FormatArg::Jsonl => ExportFormat::JsonLines,
+            FormatArg::Csv => ExportFormat::Csv
+        }
+    }
+}
+
+#[cfg(feature = "visual")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VisualLoggerArg {
+    Iteration,
+    Ant,
+    Change,
+    None
+}
+
+#[cfg(feature = "visual")]
+impl From<VisualLoggerArg> for MacroquadLogger {
+    fn from(arg: VisualLoggerArg) -> Self {
+        match arg {
+            VisualLoggerArg::Iteration => MacroquadLogger::new(DrawOn::Iteration),
+            VisualLoggerArg::Ant => MacroquadLogger::new(DrawOn::Ant),
+            VisualLoggerArg::Change => MacroquadLogger::new(DrawOn::Change),
+            VisualLoggerArg::None => MacroquadLogger::new(DrawOn::None)
+        }
+    }
+}
+
+/* Os ótimos dos benchmarks são do modelo HP na rede quadrada e não valem para os outros casos */
+pub fn known_optimum(optimum: Option<Energy>, protein: &Protein, config: &ACOConfig) -> Option<Energy> {
+    optimum.filter(|_| config.lattice == LatticeKind::Square && *protein.model() == *EnergyModel::hp())
+}
+
+pub fn exit_with_error<E: Display, T>(e: E) -> T {
+    eprintln!("{}", e);
+    process::exit(1);
+}
+
+/* Seed da execução `run`: consecutiva a partir da seed base, ou aleatória se nenhuma foi informada */
+pub fn run_seed(base: Option<u64>, run: u64) -> u64 {
+    base.map_or_else(rand::random, |seed| seed.wrapping_add(run))
+}
@@ -0,0 +1,232 @@
+use std::{collections::VecDeque, iter::Sum, ops::AddAssign};
+
+use serde::{Deserialize, Serialize};
+
+use crate::conformation::{construction::Side, grid::PointSet, lattice::{add, Point}, Conformation, Construction, Direction};
+
+/// Valor padrão de `ACOConfig::backtrack_depth`.
+pub const DEFAULT_BACKTRACK_DEPTH: u16 = 8;
+
+/// O que a formiga faz quando nenhuma direção é válida.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backtracking {
+    /* Desfaz metade da cadeia */
+    #[default]
+    Halving,
+    /* Desfaz só o último passo e não repete a direção dele */
+    Chronological,
+    /* Cronológico até `backtrack_depth` passos atrás de onde começou a falhar; depois desfaz metade */
+    LimitedDepth
+}
+
+/// Como a formiga descarta posições que prendem a cadeia.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lookahead {
+    /* A posição precisa de um vizinho livre */
+    #[default]
+    Neighbour,
+    /* As posições livres alcançáveis a partir dela precisam caber o resto da cadeia */
+    FloodFill
+}
+
+/// Contadores da construção de uma conformação, somados por iteração nas estatísticas.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ConstructionStats {
+    /* Vezes em que a formiga ficou sem saída */
+    pub backtracks: u32,
+    /* Passos desfeitos nesses retrocessos */
+    pub undone_steps: u32,
+    /* Posições livres descartadas pelo `Lookahead` */
+    pub lookahead_rejections: u32
+}
+
+impl AddAssign for ConstructionStats {
+    fn add_assign(&mut self, other: Self) {
+        self.backtracks += other.backtracks;
+        self.undone_steps += other.undone_steps;
+        self.lookahead_rejections += other.lookahead_rejections;
+    }
+}
+
+impl Sum for ConstructionStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, stats| {
+            total += stats;
+            total
+        })
+    }
+}
+
+impl Conformation {
+    pub fn construction_stats(&self) -> ConstructionStats {
+        self.stats.get()
+    }
+
+    fn update_stats(&self, f: impl FnOnce(&mut ConstructionStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+
+    /// Volta atrás quando `grow` não encontra direção válida, conforme `ACOConfig::backtracking`.
+    pub fn rewind(&mut self) {
+        self.update_stats(|stats| stats.backtracks += 1);
+
+        match self.config.backtracking {
+            Backtracking::Halving => self.rewind_halving(),
+            Backtracking::Chronological => self.rewind_chronological(),
+            Backtracking::LimitedDepth => {
+                /* Uma falha além da última sequência de retrocessos começa outra */
+                if self.i >= self.backtrack_from {
+                    self.backtrack_from = self.i;
+                }
+
+                if self.backtrack_from - self.i < self.config.backtrack_depth as usize {
+                    self.rewind_chronological();
+                } else {
+                    self.tabu.fill(0);
+                    self.backtrack_from = 0;
+                    self.rewind_halving();
+                }
+            }
+        }
+    }
+
+    fn rewind_halving(&mut self) {
+        if self.config.construction == Construction::Bidirectional {
+            return self.rewind_bidirectional();
+        }
+
+        let new_i = self.i / 2;
+
+        while self.i > new_i {
+            self.undo();
+        }
+    }
+
+    /* Desfaz o último passo e o proíbe nesta profundidade; as escolhas depois dele deixam de valer */
+    fn rewind_chronological(&mut self) {
+        self.tabu[self.i] = 0;
+
+        if self.i == 0 {
+            /* Todas as opções a partir do início falharam: recomeça do zero */
+            self.started = false;
+            return;
+        }
+
+        let (side, d) = self.undo();
+        self.tabu[self.i] |= self.tabu_bit(side, d);
+    }
+
+    /* Desfaz o último aminoácido colocado, retornando de que ponta e com que direção */
+    pub(crate) fn undo(&mut self) -> (Side, Direction) {
+        let side = match self.config.construction {
+            Construction::Forward => Side::Forward,
+            Construction::Bidirectional => self.history.pop().unwrap()
+        };
+
+        let (k, slot) = match side {
+            Side::Forward => {
+                let k = self.lo + self.positions.len() - 1;
+                self.frames.pop();
+                (k, k - 2)
+            }
+            Side::Backward => {
+                let k = self.lo;
+                self.frames.remove(0);
+                self.lo = k + 1;
+                (k, k)
+            }
+        };
+
+        let pos = match side {
+            Side::Forward => self.positions.pop().unwrap(),
+            Side::Backward => self.positions.remove(0)
+        };
+
+        self.grid.clear(pos);
+        self.score -= self.score_at(k, pos, usize::MAX);
+
+        self.i -= 1;
+        self.update_stats(|stats| stats.undone_steps += 1);
+
+        (side, self.conformation[slot].take().unwrap())
+    }
+
+    fn tabu_bit(&self, side: Side, d: Direction) -> u32 {
+        let offset = match side {
+            Side::Forward => 0,
+            Side::Backward => self.lattice().direction_count()
+        };
+
+        1 << (offset + d.as_index())
+    }
+
+    /* Direções já descartadas pelo retrocesso cronológico na profundidade atual */
+    pub(crate) fn is_tabu(&self, side: Side, d: Direction) -> bool {
+        self.tabu.get(self.i).is_some_and(|&mask| mask & self.tabu_bit(side, d) != 0)
+    }
+
+    /* A posição `pos` do aminoácido `k`, ainda não marcada, deixa espaço para o resto da cadeia */
+    pub(crate) fn has_room(&self, k: usize, pos: Point) -> bool {
+        let n = self.protein.len();
+
+        /* Aminoácidos que ainda faltam depois de `k`, na ponta onde ele entra */
+        let (remaining, other_end) = if k < self.lo {
+            (k, self.positions.last().map(|&tip| (tip, n - self.lo - self.positions.len())))
+        } else {
+            (n - 1 - k, (self.lo > 0).then(|| (self.positions[0], self.lo)))
+        };
+
+        if remaining == 0 {
+            return true;
+        }
+
+        /* Na construção bidirecional esta ponta pode cercar a outra sem que um teste local perceba,
+        então a outra é sempre verificada pela busca em largura */
+        let room = self.reaches(pos, pos, remaining)
+            && other_end.is_none_or(|(tip, needed)| needed == 0 || self.flood_fill(tip, pos, needed));
+
+        if !room {
+            self.update_stats(|stats| stats.lookahead_rejections += 1);
+        }
+
+        room
+    }
+
+    /* Se a partir de `from` ainda dá para colocar `needed` aminoácidos, tratando `blocked` como ocupada */
+    fn reaches(&self, from: Point, blocked: Point, needed: usize) -> bool {
+        match self.config.lookahead {
+            Lookahead::Neighbour => self.neighbour_offsets.iter()
+                .map(|&offset| add(from, offset))
+                .any(|next| next != blocked && self.grid.is_free(next)),
+            Lookahead::FloodFill => self.flood_fill(from, blocked, needed)
+        }
+    }
+
+    /* Busca em largura pelas posições livres vizinhas de `from` até achar `needed` delas. Nas
+    redes planas a busca fica na área da grade: a borda dela nunca é ocupada, então uma região
+    que a alcança tem espaço de sobra. */
+    fn flood_fill(&self, from: Point, blocked: Point, needed: usize) -> bool {
+        let mut seen = PointSet::default();
+        let mut queue = VecDeque::from([from]);
+        seen.insert(blocked);
+
+        while let Some(pos) = queue.pop_front() {
+            for &offset in self.neighbour_offsets {
+                let next = add(pos, offset);
+
+                if self.grid.contains(next) && self.grid.is_free(next) && seen.insert(next) {
+                    if seen.len() > needed {
+                        return true;
+                    }
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        false
+    }
+}
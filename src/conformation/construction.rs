@@ -0,0 +1,181 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{conformation::{lattice::{add, sub, Frame, Point}, Conformation, Direction}, pheromones::Pheromones};
+
+/// Como as formigas constroem a conformação.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Construction {
+    /* Do primeiro aminoácido até o último */
+    #[default]
+    Forward,
+    /* A partir de um aminoácido aleatório, estendendo as duas pontas (Shmygelska e Hoos), com
+    uma matriz de feromônios para cada sentido */
+    Bidirectional
+}
+
+/* Ponta estendida em cada passo, para desfazer na ordem inversa */
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Side {
+    Forward,
+    Backward
+}
+
+impl Conformation {
+    /* Durante a construção `positions` e `frames` guardam só os aminoácidos de `lo` a `hi`. Ao
+    completar, a conformação é remontada a partir das direções, com o primeiro aminoácido na
+    origem como na construção de uma ponta só. */
+    pub(crate) fn grow_bidirectional<R: Rng + ?Sized>(&mut self, pheromones: &Pheromones, rng: &mut R) -> bool {
+        if self.is_fully_grown() {
+            return true;
+        }
+
+        let n = self.protein.len();
+
+        if !self.started {
+            self.start_at(rng.random_range(0..n - 1));
+        }
+
+        let hi = self.lo + self.positions.len() - 1;
+        let ahead = n - 1 - hi;
+        let behind = self.lo;
+
+        let forward = if ahead > 0 { self.forward_candidates() } else { Vec::new() };
+        let backward = if behind > 0 { self.backward_candidates() } else { Vec::new() };
+
+        /* Uma ponta sem saída nunca é liberada colocando mais aminoácidos, nem na outra */
+        if (ahead > 0 && forward.is_empty()) || (behind > 0 && backward.is_empty()) {
+            return false;
+        }
+
+        /* Escolhe a ponta com probabilidade proporcional ao que falta de cada lado */
+        let side = if behind == 0 || (ahead > 0 && rng.random_range(0..ahead + behind) < ahead) {
+            Side::Forward
+        } else {
+            Side::Backward
+        };
+
+        let (k, slot, candidates) = match side {
+            Side::Forward => (hi + 1, hi - 1, forward), /* A direção fica no aminoácido `hi` */
+            Side::Backward => (self.lo - 1, self.lo - 1, backward) /* e aqui no aminoácido `lo` */
+        };
+        let candidates: Vec<_> = candidates.into_iter().filter(|&(d, _, _)| !self.is_tabu(side, d)).collect();
+
+        let weight = |d, h| match side {
+            Side::Forward => pheromones.get_weight(slot, d, h),
+            Side::Backward => pheromones.get_backward_weight(slot, d, h)
+        };
+        let Some((d, frame, pos)) = self.choose(k, usize::MAX, &candidates, weight, pheromones, rng) else {
+            return false;
+        };
+
+        self.score += self.score_at(k, pos, usize::MAX);
+        self.grid.set(pos, k);
+        self.conformation[slot] = Some(d);
+
+        match side {
+            Side::Forward => {
+                self.positions.push(pos);
+                self.frames.push(frame);
+            }
+            Side::Backward => {
+                self.frames[0] = frame;
+                self.positions.insert(0, pos);
+                self.frames.insert(0, Frame::IDENTITY); /* Ainda desconhecida */
+                self.lo = k;
+            }
+        }
+
+        self.history.push(side);
+        self.i += 1;
+
+        if self.is_fully_grown() {
+            self.rebuild_from_directions();
+        }
+
+        true
+    }
+
+    /* Recomeça com `start` na origem e `start + 1` na primeira ligação */
+    fn start_at(&mut self, start: usize) {
+        let lattice = self.lattice();
+
+        for &pos in &self.positions {
+            self.grid.clear(pos);
+        }
+
+        self.started = true;
+        self.lo = start;
+        self.positions = vec![(0, 0, 0), lattice.first_bond()];
+        self.frames = vec![Frame::IDENTITY; 2];
+        self.score = 0;
+        self.grid.set((0, 0, 0), start);
+        self.grid.set(lattice.first_bond(), start + 1);
+    }
+
+    fn forward_candidates(&self) -> Vec<(Direction, Frame, Point)> {
+        let k = self.lo + self.positions.len();
+        let last = *self.positions.last().unwrap();
+        let frame = *self.frames.last().unwrap();
+        let lattice = self.lattice();
+
+        lattice.directions().into_iter()
+            .map(|d| {
+                let new_frame = lattice.turn(&frame, d);
+                (d, new_frame, add(last, lattice.heading(&new_frame)))
+            })
+            .filter(|&(_, _, pos)| self.can_place(k, pos))
+            .collect()
+    }
+
+    /* A direção é a da leitura normal no aminoácido `lo`: a orientação de quem chega em `lo` é a
+    que, girando com `d`, dá a orientação já conhecida de `lo + 1` */
+    fn backward_candidates(&self) -> Vec<(Direction, Frame, Point)> {
+        let k = self.lo - 1;
+        let first = self.positions[0];
+        let next_frame = self.frames[1];
+        let lattice = self.lattice();
+
+        lattice.directions().into_iter()
+            .map(|d| {
+                let frame = next_frame.then(&lattice.turns()[d.as_index()].inverse());
+                (d, frame, sub(first, lattice.heading(&frame)))
+            })
+            .filter(|&(_, _, pos)| self.can_place(k, pos))
+            .collect()
+    }
+
+    /* Uma ponta pode ser fechada pela outra bem depois de colocada, e desfazer só os passos mais
+    recentes não a libera. Por isso cada falha seguida sem passar da anterior desfaz o dobro, até
+    recomeçar de outro aminoácido. */
+    pub(crate) fn rewind_bidirectional(&mut self) {
+        if self.i <= self.failed_at {
+            self.failures += 1;
+        } else {
+            self.failures = 1;
+        }
+        self.failed_at = self.i;
+
+        let new_i = self.i.checked_shr(self.failures).unwrap_or(0);
+
+        while self.i > new_i {
+            self.undo();
+        }
+
+        if self.i == 0 {
+            self.started = false;
+        }
+    }
+
+    fn rebuild_from_directions(&mut self) {
+        let directions: Vec<Direction> = self.conformation.iter().map(|d| d.unwrap()).collect();
+
+        let rebuilt = Conformation::from_directions(&self.protein, self.config, &directions)
+            .expect("a construção só gera conformações válidas");
+        rebuilt.evaluations.set(self.evaluations.get());
+        rebuilt.stats.set(self.stats.get());
+
+        *self = rebuilt;
+    }
+}
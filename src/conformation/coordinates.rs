@@ -0,0 +1,212 @@
+use std::fmt;
+
+use crate::{aco::config::ACOConfig, conformation::{lattice::{add, sub, Frame, Lattice, Point}, Conformation, Direction}, protein::Protein};
+
+/// Direção absoluta de uma ligação nas redes alinhadas aos eixos: `U`, `D`, `L` ou `R` no plano
+/// e, na rede cúbica, `F` e `B` para frente e para trás no eixo z.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AbsoluteDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+    Forward,
+    Backward
+}
+
+impl AbsoluteDirection {
+    pub fn from_velocity(v: Point) -> Option<Self> {
+        match v {
+            (0, 1, 0) => Some(AbsoluteDirection::Up),
+            (0, -1, 0) => Some(AbsoluteDirection::Down),
+            (-1, 0, 0) => Some(AbsoluteDirection::Left),
+            (1, 0, 0) => Some(AbsoluteDirection::Right),
+            (0, 0, 1) => Some(AbsoluteDirection::Forward),
+            (0, 0, -1) => Some(AbsoluteDirection::Backward),
+            _ => None
+        }
+    }
+
+    pub fn velocity(&self) -> Point {
+        match self {
+            AbsoluteDirection::Up => (0, 1, 0),
+            AbsoluteDirection::Down => (0, -1, 0),
+            AbsoluteDirection::Left => (-1, 0, 0),
+            AbsoluteDirection::Right => (1, 0, 0),
+            AbsoluteDirection::Forward => (0, 0, 1),
+            AbsoluteDirection::Backward => (0, 0, -1)
+        }
+    }
+
+    pub fn char(&self) -> char {
+        match self {
+            AbsoluteDirection::Up => 'U',
+            AbsoluteDirection::Down => 'D',
+            AbsoluteDirection::Left => 'L',
+            AbsoluteDirection::Right => 'R',
+            AbsoluteDirection::Forward => 'F',
+            AbsoluteDirection::Backward => 'B'
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(AbsoluteDirection::Up),
+            'D' => Some(AbsoluteDirection::Down),
+            'L' => Some(AbsoluteDirection::Left),
+            'R' => Some(AbsoluteDirection::Right),
+            'F' => Some(AbsoluteDirection::Forward),
+            'B' => Some(AbsoluteDirection::Backward),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for AbsoluteDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.char())
+    }
+}
+
+/* Só as redes quadrada e cúbica têm todos os vizinhos sobre os eixos */
+fn has_absolute_directions(lattice: &dyn Lattice) -> bool {
+    lattice.neighbour_offsets().iter().all(|&offset| AbsoluteDirection::from_velocity(offset).is_some())
+}
+
+/// Converte direções relativas em absolutas. A primeira ligação, fixa, aponta para a direita,
+/// então o resultado tem uma direção a mais que a entrada. Retorna `None` em redes que não
+/// são alinhadas aos eixos.
+pub fn to_absolute(lattice: &dyn Lattice, relative: &[Direction]) -> Option<Vec<AbsoluteDirection>> {
+    if !has_absolute_directions(lattice) {
+        return None;
+    }
+
+    let mut frame = Frame::IDENTITY;
+    let mut absolute = vec![AbsoluteDirection::from_velocity(lattice.first_bond())?];
+
+    for &d in relative {
+        frame = lattice.turn(&frame, d);
+        absolute.push(AbsoluteDirection::from_velocity(lattice.heading(&frame))?);
+    }
+
+    Some(absolute)
+}
+
+/// Converte direções absolutas em relativas, tomando a primeira ligação como referência.
+/// Retorna `None` se alguma ligação voltar sobre a anterior ou se a rede não for alinhada aos eixos.
+pub fn to_relative(lattice: &dyn Lattice, absolute: &[AbsoluteDirection]) -> Option<Vec<Direction>> {
+    if !has_absolute_directions(lattice) {
+        return None;
+    }
+
+    let Some(first) = absolute.first() else {
+        return Some(Vec::new());
+    };
+
+    let mut frame = lattice.frame_towards(first.velocity(), &Frame::IDENTITY)?;
+
+    absolute[1..].iter()
+        .map(|next| {
+            let d = lattice.direction_to(&frame, next.velocity())?;
+            frame = lattice.turn(&frame, d);
+            Some(d)
+        })
+        .collect()
+}
+
+impl Conformation {
+    /// Coordenadas dos aminoácidos já colocados, a partir de `first_placed`. Na conformação
+    /// completa o primeiro fica sempre em `(0, 0, 0)` e o segundo em `Lattice::first_bond`. Nas
+    /// redes planas `z` é sempre 0, e a rede triangular usa coordenadas axiais (veja
+    /// `Lattice::to_cartesian`).
+    pub fn coordinates(&self) -> &[Point] {
+        &self.positions
+    }
+
+    /// Índice do aminoácido de `coordinates()[0]`; só é diferente de 0 no meio da construção
+    /// bidirecional.
+    pub fn first_placed(&self) -> usize {
+        self.lo
+    }
+
+    /// `None` em redes que não são alinhadas aos eixos.
+    pub fn absolute_directions(&self) -> Option<Vec<AbsoluteDirection>> {
+        if !has_absolute_directions(self.lattice()) {
+            return None;
+        }
+
+        self.positions.windows(2)
+            .map(|pair| AbsoluteDirection::from_velocity(sub(pair[1], pair[0])))
+            .collect()
+    }
+
+    /// Monta uma conformação completa a partir das direções relativas. Retorna `None` se a
+    /// quantidade de direções não bater com a proteína, se alguma direção não existir na rede
+    /// da configuração ou se houver sobreposição.
+    pub fn from_directions(protein: &Protein, config: ACOConfig, directions: &[Direction]) -> Option<Self> {
+        Conformation::from_partial_directions(protein, config, directions)
+            .filter(Conformation::is_fully_grown)
+    }
+
+    /// Como `from_directions`, aceitando só as primeiras direções, como numa conformação ainda
+    /// em construção.
+    pub fn from_partial_directions(protein: &Protein, config: ACOConfig, directions: &[Direction]) -> Option<Self> {
+        let mut conformation = Conformation::new(protein, config);
+
+        if directions.len() > conformation.conformation.len() {
+            return None;
+        }
+
+        let lattice = config.lattice.get();
+
+        for &d in directions {
+            if d.as_index() >= lattice.direction_count() {
+                return None;
+            }
+
+            let k = conformation.i + 2;
+            let frame = lattice.turn(&conformation.frames[k - 1], d);
+            let pos = add(conformation.positions[k - 1], lattice.heading(&frame));
+
+            if !conformation.grid.is_free(pos) {
+                return None;
+            }
+
+            conformation.place(d, frame, pos);
+        }
+
+        Some(conformation)
+    }
+
+    /// Como `from_directions`, lendo uma string com as letras de `Lattice::direction_chars`,
+    /// como a gerada por `to_string`.
+    pub fn from_relative_str(protein: &Protein, config: ACOConfig, s: &str) -> Option<Self> {
+        Conformation::from_partial_relative_str(protein, config, s).filter(Conformation::is_fully_grown)
+    }
+
+    /// Como `from_partial_directions`, lendo uma string como a de `to_partial_string`.
+    pub fn from_partial_relative_str(protein: &Protein, config: ACOConfig, s: &str) -> Option<Self> {
+        let lattice = config.lattice.get();
+        let directions: Option<Vec<Direction>> = s.chars().map(|c| lattice.direction_from_char(c)).collect();
+        Conformation::from_partial_directions(protein, config, &directions?)
+    }
+
+    /// Como `to_string`, com as direções já escolhidas até a primeira que falta. Na construção
+    /// bidirecional, as direções depois dessa lacuna ficam de fora.
+    pub fn to_partial_string(&self) -> String {
+        let lattice = self.lattice();
+        self.conformation.iter()
+            .map_while(|dir| dir.map(|dir| lattice.direction_char(dir)))
+            .collect()
+    }
+
+    /// Como `from_directions`, lendo uma string `U`/`D`/`L`/`R` (e `F`/`B` na rede cúbica) com uma direção por ligação.
+    pub fn from_absolute_str(protein: &Protein, config: ACOConfig, s: &str) -> Option<Self> {
+        let absolute: Option<Vec<AbsoluteDirection>> = s.chars().map(AbsoluteDirection::from_char).collect();
+        Conformation::from_directions(protein, config, &to_relative(config.lattice.get(), &absolute?)?)
+    }
+
+    pub fn to_absolute_string(&self) -> Option<String> {
+        Some(self.absolute_directions()?.iter().map(AbsoluteDirection::char).collect())
+    }
+}
@@ -1,78 +1,83 @@
 use macroquad::{shapes::{draw_circle, draw_circle_lines, draw_line}, text::draw_text, window::{clear_background, next_frame, screen_height, screen_width}};
 
-use crate::{conformation::Conformation, pheromones::Pheromones, protein::AminoAcid};
+use crate::{aco::logger::{default::Labels, optimum_suffix}, conformation::{lattice::{Lattice, Point}, Conformation}, pheromones::Pheromones, protein::{AminoAcid, Energy}};
+
+/* Projeção oblíqua da posição real: o eixo z aparece na diagonal, e nas redes planas nada muda */
+fn project(lattice: &dyn Lattice, loc: Point, screen_center: (f32, f32), scale: f32) -> (f32, f32) {
+    let (x, y, z) = lattice.to_cartesian(loc);
+    let depth = z * 0.4;
+
+    (
+        screen_center.0 + (x + depth) * scale,
+        screen_center.1 - (y + depth) * scale // inverter y para cima no grid
+    )
+}
 
 impl Conformation {
-    pub async fn draw(&self, iteration: u16, best: f64, pheromones: Option<&Pheromones>) {
-        clear_background(macroquad::color::WHITE);
+    pub async fn draw(&self, iteration: u16, best: Energy, pheromones: Option<&Pheromones>) {
+        self.draw_frame(iteration, best, pheromones);
+        next_frame().await;
+    }
 
-        draw_text(&format!("Iteração: {}", iteration), 10.0, 25.0, 30.0, macroquad::color::BLACK);
-        draw_text(&format!("Melhor: {}", best), 13.0, 45.0, 30.0, macroquad::color::BLACK);
+    /// Como `draw`, sem esperar o próximo quadro, para desenhar mais coisas por cima.
+    pub fn draw_frame(&self, iteration: u16, best: Energy, pheromones: Option<&Pheromones>) {
+        clear_background(macroquad::color::WHITE);
 
-        let mut loc = (1, 0); /* Inicializa posição do agente */
-        let mut v = (1, 0);   /* Inicializa velocidade do agente */
+        draw_text(format!("Iteração: {}", iteration), 10.0, 25.0, 30.0, macroquad::color::BLACK);
+        draw_text(format!("Melhor: {}{}", best, optimum_suffix(self.config, best, &Labels::PORTUGUESE)), 13.0, 45.0, 30.0, macroquad::color::BLACK);
 
         let scale = 17.0;
         let screen_center = (screen_width() / 2.0, screen_height() / 2.0);
         let multi = 0.3; /* Tamanho dos círculos */
 
-        // Desenha aminoácidos fixos
-        self.draw_amino_acid(self.protein[0], (0, 0), screen_center, scale, multi);
-        self.draw_amino_acid(self.protein[1], (1, 0), screen_center, scale, multi);
+        let coordinates = self.coordinates();
 
-        // Desenha linha que liga aminoácidos fixos
-        self.draw_line((0, 0), (1, 0), screen_center, scale);
-
-        for (i, direction) in self.conformation.iter().enumerate() {
-            if let Some(direction) = direction {
-                v = Conformation::get_new_velocity(v, *direction); /* Calcula nova velocidade */
-                
-                let new_loc = (loc.0 + v.0, loc.1 + v.1); /* Calcula nova posição do agente */
-                
-                self.draw_line(loc, new_loc, screen_center, scale);
-                self.draw_amino_acid(self.protein[i + 2], new_loc, screen_center, scale, multi);
-                
-                loc = new_loc;
-            }
+        for pair in coordinates.windows(2) {
+            self.draw_line(pair[0], pair[1], screen_center, scale);
         }
 
-        pheromones.map(|p| p.draw());
+        for (i, &loc) in coordinates.iter().enumerate() {
+            self.draw_amino_acid(self.protein[self.first_placed() + i], loc, screen_center, scale, multi);
+        }
 
-        next_frame().await;
+        if let Some(pheromones) = pheromones {
+            pheromones.draw();
+        }
     }
 
     fn draw_amino_acid(
         &self,
         amino_acid: AminoAcid,
-        loc: (i32, i32),
+        loc: Point,
         screen_center: (f32, f32),
         scale: f32,
         multi: f32
     ) {
-        if amino_acid == AminoAcid::Hydrophobic {
-            draw_circle(
-                screen_center.0 + loc.0 as f32 * scale,
-                screen_center.1 - loc.1 as f32 * scale, // inverter y para cima no grid
-                scale * multi + 0.1,
-                macroquad::color::BLACK
-            );
+        let (x, y) = project(self.lattice(), loc, screen_center, scale);
+
+        /* Preenchido quando o contato consigo mesmo é favorável, como o H no modelo HP */
+        if self.protein.model().interaction(amino_acid, amino_acid) < 0 {
+            draw_circle(x, y, scale * multi + 0.1, macroquad::color::BLACK);
         }
         
         draw_circle_lines(
-            screen_center.0 + loc.0 as f32 * scale,
-            screen_center.1 - loc.1 as f32 * scale, // inverter y para cima no grid
+            x,
+            y,
             scale * multi,
             2.0,
             macroquad::color::BLACK
         );
     }
 
-    fn draw_line(&self, start: (i32, i32), end: (i32, i32), screen_center: (f32, f32), scale: f32) {
+    fn draw_line(&self, start: Point, end: Point, screen_center: (f32, f32), scale: f32) {
+        let start = project(self.lattice(), start, screen_center, scale);
+        let end = project(self.lattice(), end, screen_center, scale);
+
         draw_line(
-            screen_center.0 + start.0 as f32 * scale,
-            screen_center.1 - start.1 as f32 * scale,
-            screen_center.0 + end.0 as f32 * scale,
-            screen_center.1 - end.1 as f32 * scale,                            
+            start.0,
+            start.1,
+            end.0,
+            end.1,
             2.0,
             macroquad::color::BLACK
         );
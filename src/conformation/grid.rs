@@ -0,0 +1,116 @@
+use std::{collections::{HashMap, HashSet}, hash::{BuildHasherDefault, Hasher}};
+
+use crate::conformation::lattice::{Lattice, Point};
+
+const EMPTY: u32 = u32::MAX;
+
+/* Hash simples (no estilo do FxHash) para coordenadas: o SipHash padrão domina o tempo nas redes espaciais */
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PointHasher(u64);
+
+impl Hasher for PointHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(byte as u64);
+        }
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u64(i as u32 as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = (self.0.rotate_left(5) ^ i).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+type PointMap = HashMap<Point, u32, BuildHasherDefault<PointHasher>>;
+pub(crate) type PointSet = HashSet<Point, BuildHasherDefault<PointHasher>>;
+
+/// Ocupação da rede: guarda o índice do aminoácido em cada posição.
+///
+/// Como o primeiro aminoácido fica sempre na origem, uma cadeia de `n` aminoácidos nunca sai
+/// de `[-n, n]²` (também em coordenadas axiais, na rede triangular), então nas redes planas uma
+/// matriz densa com uma borda extra permite consultar vizinhos sem checar limites. Nas redes
+/// espaciais essa matriz teria `(2n + 3)³` posições, então só as posições ocupadas são guardadas.
+#[derive(Debug, Clone)]
+pub enum OccupancyGrid {
+    Dense {
+        cells: Vec<u32>,
+        offset: i32,
+        side: usize
+    },
+    Sparse(PointMap)
+}
+
+impl OccupancyGrid {
+    pub fn new(len: usize, lattice: &dyn Lattice) -> Self {
+        if !lattice.is_planar() {
+            return OccupancyGrid::Sparse(PointMap::with_capacity_and_hasher(len, Default::default()));
+        }
+
+        let offset = len as i32 + 1;
+        let side = 2 * offset as usize + 1;
+
+        OccupancyGrid::Dense {
+            cells: vec![EMPTY; side * side],
+            offset,
+            side
+        }
+    }
+
+    fn index(pos: Point, offset: i32, side: usize) -> usize {
+        debug_assert_eq!(pos.2, 0, "posição fora do plano");
+        (pos.0 + offset) as usize + (pos.1 + offset) as usize * side
+    }
+
+    pub fn get(&self, pos: Point) -> Option<usize> {
+        let i = match self {
+            OccupancyGrid::Dense { cells, offset, side } => cells[Self::index(pos, *offset, *side)],
+            OccupancyGrid::Sparse(cells) => return Self::get_sparse(cells, pos)
+        };
+
+        (i != EMPTY).then_some(i as usize)
+    }
+
+    /* Fora da função principal para não atrapalhar a otimização do caso denso */
+    #[inline(never)]
+    fn get_sparse(cells: &PointMap, pos: Point) -> Option<usize> {
+        cells.get(&pos).map(|&i| i as usize)
+    }
+
+    /// Se `pos` está dentro da área guardada; nas redes espaciais, sempre. Uma cadeia nunca sai
+    /// dela, mas buscas pelas posições livres em volta podem sair.
+    pub fn contains(&self, pos: Point) -> bool {
+        match self {
+            OccupancyGrid::Dense { offset, .. } => pos.0.abs() <= *offset && pos.1.abs() <= *offset,
+            OccupancyGrid::Sparse(_) => true
+        }
+    }
+
+    pub fn is_free(&self, pos: Point) -> bool {
+        self.get(pos).is_none()
+    }
+
+    pub fn set(&mut self, pos: Point, i: usize) {
+        match self {
+            OccupancyGrid::Dense { cells, offset, side } => cells[Self::index(pos, *offset, *side)] = i as u32,
+            OccupancyGrid::Sparse(cells) => {
+                cells.insert(pos, i as u32);
+            }
+        }
+    }
+
+    pub fn clear(&mut self, pos: Point) {
+        match self {
+            OccupancyGrid::Dense { cells, offset, side } => cells[Self::index(pos, *offset, *side)] = EMPTY,
+            OccupancyGrid::Sparse(cells) => {
+                cells.remove(&pos);
+            }
+        }
+    }
+}
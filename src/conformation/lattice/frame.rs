@@ -0,0 +1,59 @@
+use crate::conformation::lattice::{add, sub, Point};
+
+/// Orientação do agente ao chegar em um aminoácido: a transformação linear que leva a
+/// orientação inicial à atual. Também é usada para girar trechos da cadeia.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Frame([[i32; 3]; 3]);
+
+impl Frame {
+    pub const IDENTITY: Frame = Frame([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    pub const fn new(matrix: [[i32; 3]; 3]) -> Self {
+        Self(matrix)
+    }
+
+    pub fn apply(&self, v: Point) -> Point {
+        let [x, y, z] = self.0.map(|row| row[0] * v.0 + row[1] * v.1 + row[2] * v.2);
+        (x, y, z)
+    }
+
+    /// Composição `self · other`: aplica `other` e depois `self`.
+    pub fn then(&self, other: &Frame) -> Frame {
+        let mut matrix = [[0; 3]; 3];
+
+        for (r, row) in matrix.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.0[r][k] * other.0[k][c]).sum();
+            }
+        }
+
+        Frame(matrix)
+    }
+
+    /* Simetrias da rede têm determinante ±1, então a inversa (adjunta / determinante) é inteira */
+    pub fn inverse(&self) -> Frame {
+        let m = &self.0;
+        let cofactor = |r: usize, c: usize| {
+            let (r1, r2) = ((r + 1) % 3, (r + 2) % 3);
+            let (c1, c2) = ((c + 1) % 3, (c + 2) % 3);
+            m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1]
+        };
+
+        let det: i32 = (0..3).map(|c| m[0][c] * cofactor(0, c)).sum();
+        debug_assert!(det.abs() == 1, "transformação sem inversa inteira");
+
+        let mut matrix = [[0; 3]; 3];
+        for (r, row) in matrix.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = cofactor(c, r) * det;
+            }
+        }
+
+        Frame(matrix)
+    }
+
+    /* Aplica a transformação em `pos` tomando `pivot` como origem */
+    pub fn rotate_around(&self, pos: Point, pivot: Point) -> Point {
+        add(pivot, self.apply(sub(pos, pivot)))
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::conformation::Direction;
+
+mod frame;
+mod planar;
+mod spatial;
+
+pub use frame::Frame;
+pub use planar::{Square, Triangular};
+pub use spatial::{Cubic, Fcc};
+
+/// Posição na rede. Nas redes planas `z` é sempre 0.
+pub type Point = (i32, i32, i32);
+
+/// Rede onde a proteína é dobrada.
+///
+/// Cada rede descreve só os seus dados: vizinhos, a primeira ligação e o giro de cada direção
+/// relativa. Orientações são transformações lineares inteiras (simetrias da rede), então andar,
+/// girar trechos da cadeia e converter posições em direções funcionam igual em todas.
+pub trait Lattice: fmt::Debug + Send + Sync {
+    fn neighbour_offsets(&self) -> &[Point];
+
+    /// Giro de cada direção relativa, na ordem dos índices de `Direction`: quem chega com a
+    /// orientação `frame` e toma a direção `d` fica com a orientação `frame.then(&turns()[d])`.
+    fn turns(&self) -> &[Frame];
+
+    /// Letra de cada direção relativa, na mesma ordem de `turns`.
+    fn direction_chars(&self) -> &'static str;
+
+    fn is_planar(&self) -> bool;
+
+    /// Posição do segundo aminoácido, que fica fixo junto com o primeiro na origem.
+    fn first_bond(&self) -> Point {
+        (1, 0, 0)
+    }
+
+    /// Posição no espaço de um ponto da rede, usada para desenhar.
+    fn to_cartesian(&self, p: Point) -> (f32, f32, f32) {
+        (p.0 as f32, p.1 as f32, p.2 as f32)
+    }
+
+    fn direction_count(&self) -> usize {
+        self.turns().len()
+    }
+
+    fn directions(&self) -> Vec<Direction> {
+        (0..self.direction_count()).map(Direction::from_index).collect()
+    }
+
+    fn turn(&self, frame: &Frame, d: Direction) -> Frame {
+        frame.then(&self.turns()[d.as_index()])
+    }
+
+    /// Para onde anda quem está com a orientação `frame`.
+    fn heading(&self, frame: &Frame) -> Point {
+        frame.apply(self.first_bond())
+    }
+
+    /// Direção relativa que leva a andar em `heading`. Retorna `None` se não houver nenhuma,
+    /// como ao voltar para trás.
+    fn direction_to(&self, frame: &Frame, heading: Point) -> Option<Direction> {
+        /* Compara na orientação inicial, onde cada direção anda em `turns()[d] · first_bond` */
+        let local = frame.inverse().apply(heading);
+
+        self.turns().iter()
+            .position(|turn| turn.apply(self.first_bond()) == local)
+            .map(Direction::from_index)
+    }
+
+    /// Orientação que anda em `heading`, escolhida entre as simetrias geradas pelos giros e
+    /// mantendo `hint` quando ele já serve.
+    fn frame_towards(&self, heading: Point, hint: &Frame) -> Option<Frame> {
+        let mut group = vec![Frame::IDENTITY];
+        let mut k = 0;
+
+        /* Busca em largura pelas composições dos giros */
+        while k < group.len() {
+            let frame = hint.then(&group[k]);
+            if self.heading(&frame) == heading {
+                return Some(frame);
+            }
+
+            for turn in self.turns() {
+                let g = group[k].then(turn);
+                if !group.contains(&g) {
+                    group.push(g);
+                }
+            }
+            k += 1;
+        }
+
+        None
+    }
+
+    /// Para cada direção, a que quem lê a cadeia ao contrário toma no mesmo aminoácido, usada
+    /// pela matriz de feromônios do sentido inverso. Nas redes planas troca esquerda e direita;
+    /// nas espaciais a leitura inversa também depende das orientações anteriores, então é só uma
+    /// aproximação, e se ela não for uma permutação (como na FCC) cada direção fica com ela mesma.
+    fn reverse_directions(&self) -> Vec<Direction> {
+        let first_bond = self.first_bond();
+        let identity = self.directions();
+
+        let Some(back) = self.frame_towards(sub((0, 0, 0), first_bond), &Frame::IDENTITY) else {
+            return identity;
+        };
+
+        /* Quem volta chega em `j` virado para trás da orientação de `j + 1` e sai na ligação de chegada de `j` */
+        let reverse: Option<Vec<Direction>> = self.turns().iter()
+            .map(|turn| {
+                let local = back.inverse().then(&turn.inverse()).then(&back).apply(first_bond);
+                self.direction_to(&Frame::IDENTITY, local)
+            })
+            .collect();
+
+        match reverse {
+            Some(reverse) if identity.iter().all(|d| reverse.contains(d)) => reverse,
+            _ => identity
+        }
+    }
+
+    fn are_adjacent(&self, a: Point, b: Point) -> bool {
+        self.neighbour_offsets().contains(&sub(b, a))
+    }
+
+    fn direction_char(&self, d: Direction) -> char {
+        self.direction_chars().chars().nth(d.as_index()).unwrap()
+    }
+
+    fn direction_from_char(&self, c: char) -> Option<Direction> {
+        self.direction_chars().chars().position(|label| label == c).map(Direction::from_index)
+    }
+}
+
+/// Rede escolhida na configuração.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatticeKind {
+    /* Modelo 2D-HP */
+    #[default]
+    Square,
+    Triangular,
+    /* Modelo 3D-HP */
+    Cubic,
+    Fcc
+}
+
+impl LatticeKind {
+    pub fn get(&self) -> &'static dyn Lattice {
+        match self {
+            LatticeKind::Square => &Square,
+            LatticeKind::Triangular => &Triangular,
+            LatticeKind::Cubic => &Cubic,
+            LatticeKind::Fcc => &Fcc
+        }
+    }
+}
+
+pub(crate) fn add(a: Point, b: Point) -> Point {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+pub(crate) fn sub(a: Point, b: Point) -> Point {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
@@ -0,0 +1,60 @@
+use crate::conformation::lattice::{Frame, Lattice, Point};
+
+/* Giros em torno do eixo z */
+const QUARTER_LEFT: Frame = Frame::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+const QUARTER_RIGHT: Frame = Frame::new([[0, 1, 0], [-1, 0, 0], [0, 0, 1]]);
+
+/// Rede quadrada do modelo 2D-HP, com as direções `L`, `S` e `R`.
+#[derive(Debug, Clone, Copy)]
+pub struct Square;
+
+impl Lattice for Square {
+    fn neighbour_offsets(&self) -> &[Point] {
+        &[(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0)]
+    }
+
+    fn turns(&self) -> &[Frame] {
+        &[QUARTER_LEFT, Frame::IDENTITY, QUARTER_RIGHT]
+    }
+
+    fn direction_chars(&self) -> &'static str {
+        "LSR"
+    }
+
+    fn is_planar(&self) -> bool {
+        true
+    }
+}
+
+/* Giros de 60° em coordenadas axiais, onde os vizinhos são (±1, 0), (0, ±1) e ±(1, -1) */
+const SIXTH_LEFT: Frame = Frame::new([[0, -1, 0], [1, 1, 0], [0, 0, 1]]);
+const SIXTH_RIGHT: Frame = Frame::new([[1, 1, 0], [-1, 0, 0], [0, 0, 1]]);
+const THIRD_LEFT: Frame = Frame::new([[-1, -1, 0], [1, 0, 0], [0, 0, 1]]);
+const THIRD_RIGHT: Frame = Frame::new([[0, 1, 0], [-1, -1, 0], [0, 0, 1]]);
+
+/// Rede triangular, em que cada posição tem seis vizinhos. Além de `L`, `S` e `R` (giros de 60°),
+/// `l` e `r` são as curvas fechadas de 120°. As posições usam coordenadas axiais.
+#[derive(Debug, Clone, Copy)]
+pub struct Triangular;
+
+impl Lattice for Triangular {
+    fn neighbour_offsets(&self) -> &[Point] {
+        &[(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (1, -1, 0), (-1, 1, 0)]
+    }
+
+    fn turns(&self) -> &[Frame] {
+        &[SIXTH_LEFT, Frame::IDENTITY, SIXTH_RIGHT, THIRD_LEFT, THIRD_RIGHT]
+    }
+
+    fn direction_chars(&self) -> &'static str {
+        "LSRlr"
+    }
+
+    fn is_planar(&self) -> bool {
+        true
+    }
+
+    fn to_cartesian(&self, p: Point) -> (f32, f32, f32) {
+        (p.0 as f32 + p.1 as f32 / 2.0, p.1 as f32 * 3f32.sqrt() / 2.0, 0.0)
+    }
+}
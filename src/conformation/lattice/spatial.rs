@@ -0,0 +1,105 @@
+use std::sync::LazyLock;
+
+use crate::conformation::lattice::{Frame, Lattice, Point};
+
+const QUARTER_LEFT: Frame = Frame::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+const QUARTER_RIGHT: Frame = Frame::new([[0, 1, 0], [-1, 0, 0], [0, 0, 1]]);
+/* Giros em torno do eixo y: a frente vai para o cima e o cima para trás (e o contrário) */
+const QUARTER_UP: Frame = Frame::new([[0, 0, -1], [0, 1, 0], [1, 0, 0]]);
+const QUARTER_DOWN: Frame = Frame::new([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]);
+
+/// Rede cúbica do modelo 3D-HP. Além de `L`, `S` e `R`, `U` e `D` sobem e descem em relação
+/// ao "cima" do agente, que começa em `+z`.
+#[derive(Debug, Clone, Copy)]
+pub struct Cubic;
+
+impl Lattice for Cubic {
+    fn neighbour_offsets(&self) -> &[Point] {
+        &[(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)]
+    }
+
+    fn turns(&self) -> &[Frame] {
+        &[QUARTER_LEFT, Frame::IDENTITY, QUARTER_RIGHT, QUARTER_UP, QUARTER_DOWN]
+    }
+
+    fn direction_chars(&self) -> &'static str {
+        "LSRUD"
+    }
+
+    fn is_planar(&self) -> bool {
+        false
+    }
+}
+
+const FCC_OFFSETS: [Point; 12] = [
+    (1, 1, 0), (1, -1, 0), (-1, 1, 0), (-1, -1, 0),
+    (1, 0, 1), (1, 0, -1), (-1, 0, 1), (-1, 0, -1),
+    (0, 1, 1), (0, 1, -1), (0, -1, 1), (0, -1, -1)
+];
+
+/* Para cada vizinho, exceto o que volta para trás, a primeira rotação do cubo que leva a
+primeira ligação até ele. O primeiro vizinho é a própria ligação, então `S` é a identidade. */
+static FCC_TURNS: LazyLock<Vec<Frame>> = LazyLock::new(|| {
+    let rotations = cube_rotations();
+    let first_bond = FCC_OFFSETS[0];
+
+    FCC_OFFSETS.iter()
+        .filter(|&&v| v != (-first_bond.0, -first_bond.1, -first_bond.2))
+        .map(|&v| *rotations.iter().find(|r| r.apply(first_bond) == v).unwrap())
+        .collect()
+});
+
+/// Rede cúbica de faces centradas, em que cada posição tem doze vizinhos. As onze direções
+/// relativas são `S` (seguir reto) e as letras de `A` a `J`.
+#[derive(Debug, Clone, Copy)]
+pub struct Fcc;
+
+impl Lattice for Fcc {
+    fn neighbour_offsets(&self) -> &[Point] {
+        &FCC_OFFSETS
+    }
+
+    fn turns(&self) -> &[Frame] {
+        &FCC_TURNS
+    }
+
+    fn direction_chars(&self) -> &'static str {
+        "SABCDEFGHIJ"
+    }
+
+    fn is_planar(&self) -> bool {
+        false
+    }
+
+    fn first_bond(&self) -> Point {
+        FCC_OFFSETS[0]
+    }
+}
+
+/* As 24 rotações do cubo (permutações dos eixos com sinais e determinante 1), começando pela identidade */
+fn cube_rotations() -> Vec<Frame> {
+    const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+    let mut rotations = Vec::with_capacity(24);
+
+    for permutation in PERMUTATIONS {
+        for signs in 0..8 {
+            let mut matrix = [[0; 3]; 3];
+            for (r, &c) in permutation.iter().enumerate() {
+                matrix[r][c] = if signs & (1 << r) == 0 { 1 } else { -1 };
+            }
+
+            if determinant(&matrix) == 1 {
+                rotations.push(Frame::new(matrix));
+            }
+        }
+    }
+
+    rotations
+}
+
+fn determinant(m: &[[i32; 3]; 3]) -> i32 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
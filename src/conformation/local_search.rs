@@ -1,47 +1,59 @@
-use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
 
 use crate::conformation::{Conformation, Direction};
 
+/// Vizinhança usada na busca local.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Neighbourhood {
+    /* Mutação pontual e macro mutação sobre as direções relativas */
+    #[default]
+    Pivot,
+    PullMove,
+    /* As duas anteriores em sequência */
+    Hybrid
+}
+
 impl Conformation {
-    pub fn local_search(&mut self, rng: &mut ThreadRng) -> bool {
+    pub fn local_search<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
         let mut improved = false;
 
-        improved = self.point_mutation(rng) || improved;
-        improved = self.macro_mutation_neightbourhood(rng) || improved;
+        if self.config.neighbourhood != Neighbourhood::PullMove {
+            improved = self.point_mutation(rng) || improved;
+            improved = self.macro_mutation_neightbourhood(rng) || improved;
+        }
+
+        if self.config.neighbourhood != Neighbourhood::Pivot {
+            improved = self.pull_move_search(rng) || improved;
+        }
         
         improved
     }
 
-    fn point_mutation(&mut self, rng: &mut ThreadRng) -> bool {
+    fn point_mutation<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
         let mut indexes: Vec<usize> = (0..self.conformation.len()).collect();
         indexes.shuffle(rng); /* Embraralha os índices */
 
-        let mut current_fitness = self.eval(); /* Pega fitness para comparação */
         let mut improved = false; /* Variável apra verificar se houve melhora */
 
-        let directions: Vec<Direction> = Direction::vec();
+        let directions = self.lattice().directions();
 
         for i in indexes {
-            let mut original_direction = self.conformation[i]; /* Salva direção original para reverção */
-
             for &d in &directions {
-                self.conformation[i] = Some(d); /* Tenta uma nova direção */
-
-                if !self.is_valid() { /* Verica se gera sobreposição */
-                    self.conformation[i] = original_direction; /* Reverte se for inválido */
+                if self.conformation[i] == Some(d) {
                     continue;
                 }
 
-                let new_fitness = self.eval(); /* Faz nova avaliação */
+                let Some(delta) = self.pivot_delta(i, d) else {
+                    continue; /* Gera sobreposição */
+                };
 
-                if new_fitness > current_fitness {
-                    current_fitness = new_fitness;
-                    original_direction = self.conformation[i];
+                if delta > 0 {
+                    self.apply_pivot(i, d, delta);
                     improved = true;
-                } else if new_fitness == current_fitness && rng.random::<f64>() <= self.config.neutral_mutation_rate {
-                    original_direction = self.conformation[i];
-                } else {
-                    self.conformation[i] = original_direction; /* Reverte se não melhorar */
+                } else if delta == 0 && rng.random::<f64>() <= self.config.neutral_mutation_rate {
+                    self.apply_pivot(i, d, delta);
                 }
             }
         }
@@ -49,50 +61,82 @@ impl Conformation {
         improved
     }
 
-    fn macro_mutation_neightbourhood(&mut self, rng: &mut ThreadRng) -> bool {
-        let backup = self.conformation.clone();
-        let fitness = self.eval();
-
+    fn macro_mutation_neightbourhood<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
         let n = self.conformation.len();
 
         let mut i = rng.random_range(0..n);
         let mut j = rng.random_range(0..n);
 
         if j < i {
-            let temp = i;
-            i = j;
-            j = temp;
+            std::mem::swap(&mut i, &mut j);
         }
 
+        let mut applied = Vec::new(); /* Giros aplicados, para reverter */
+        let mut total_delta = 0;
+
         for k in i..=j {
             let original = self.conformation[k].unwrap();
 
-            let mut new_directions: Vec<Direction> = Direction::iter().filter(|d| *d != original).collect();
+            let mut new_directions: Vec<Direction> = self.lattice().directions().into_iter()
+                .filter(|d| *d != original)
+                .collect();
             new_directions.shuffle(rng);
 
-            self.conformation[k] = Some(new_directions[0]);
-
-            if !self.is_valid() {
-                self.conformation[k] = Some(new_directions[0]);
-                
-                if !self.is_valid() {
-                    self.conformation[k] = Some(original);
+            /* Tenta as direções sorteadas até uma não gerar sobreposição */
+            for d in new_directions {
+                if let Some(delta) = self.pivot_delta(k, d) {
+                    self.apply_pivot(k, d, delta);
+                    applied.push((k, original, delta));
+                    total_delta += delta;
+                    break;
                 }
             }
         }
 
-        let new_fitness = self.eval(); 
-
-        if new_fitness > fitness {
+        if total_delta > 0 {
             true
-        } else if new_fitness == fitness {
+        } else if total_delta == 0 {
             if rng.random::<f64>() > self.config.neutral_mutation_rate {
-                self.conformation = backup;
+                self.revert(&applied);
             }
             false
         } else {
-            self.conformation = backup;
+            self.revert(&applied);
             false
         }
     }
-}
\ No newline at end of file
+
+    fn revert(&mut self, applied: &[(usize, Direction, i32)]) {
+        for &(k, original, delta) in applied.iter().rev() {
+            self.apply_pivot(k, original, -delta);
+        }
+    }
+
+    /* Para cada aminoácido, aplica o primeiro pull move que melhora (ou, às vezes, mantém) a energia */
+    fn pull_move_search<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        let mut indexes: Vec<usize> = (0..self.positions.len()).collect();
+        indexes.shuffle(rng);
+
+        let mut improved = false;
+
+        for i in indexes {
+            let mut moves = self.pull_moves(i);
+            moves.shuffle(rng);
+
+            for pull in moves {
+                let (delta, undo) = self.apply_pull(&pull);
+
+                if delta > 0 {
+                    improved = true;
+                    break;
+                } else if delta == 0 && rng.random::<f64>() <= self.config.neutral_mutation_rate {
+                    break;
+                }
+
+                self.undo_pull(undo);
+            }
+        }
+
+        improved
+    }
+}
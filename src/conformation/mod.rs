@@ -1,219 +1,320 @@
-use std::collections::{HashMap, HashSet};
-use rand::{distr::{weighted::WeightedIndex, Distribution}, rngs::ThreadRng, seq::IndexedRandom};
+use std::{cell::Cell, collections::HashSet, fmt};
+use rand::{distr::{weighted::WeightedIndex, Distribution}, seq::IndexedRandom, Rng};
 
-use crate::{aco::config::ACOConfig, pheromones::Pheromones, protein::{AminoAcid, Protein}};
+use crate::{aco::config::ACOConfig, conformation::{construction::Side, grid::OccupancyGrid, lattice::{add, Frame, Lattice, Point}}, pheromones::Pheromones, protein::{Energy, Protein}};
 
+#[cfg(feature = "visual")]
 pub mod display;
+pub mod backtracking;
+pub mod construction;
+pub mod coordinates;
+pub mod grid;
+pub mod lattice;
 pub mod local_search;
+pub mod pull_moves;
+
+pub use backtracking::{Backtracking, ConstructionStats, Lookahead};
+pub use construction::Construction;
+
+/* Peso mínimo da heurística, para posições com contatos desfavoráveis ainda poderem ser escolhidas */
+const MIN_HEURISTIC: f64 = 0.01;
 
 #[derive(Debug, Clone)]
 pub struct Conformation {
     protein: Protein,
-    pub conformation: Vec<Option<Direction>>,
+    conformation: Vec<Option<Direction>>,
     config: ACOConfig,
-    pub i: usize
+    i: usize,
+    /* Primeiro aminoácido já colocado; só passa de 0 durante a construção bidirecional */
+    lo: usize,
+    /* Ponta estendida em cada passo da construção bidirecional */
+    history: Vec<Side>,
+    /* Se a construção bidirecional já escolheu o aminoácido inicial */
+    started: bool,
+    /* Onde a construção bidirecional falhou por último e quantas vezes seguidas sem passar dali */
+    failed_at: usize,
+    failures: u32,
+    /* Direções já descartadas em cada profundidade pelo retrocesso cronológico, uma por bit
+    (depois das da frente vêm as de trás), e onde começou a sequência de retrocessos atual */
+    tabu: Vec<u32>,
+    backtrack_from: usize,
+    stats: Cell<ConstructionStats>,
+    /* Posições dos aminoácidos já colocados (de `lo` em diante), mantidas junto com `grid` e `score` a cada mudança */
+    positions: Vec<Point>,
+    /* Orientação com que o agente chegou a cada aminoácido */
+    frames: Vec<Frame>,
+    grid: OccupancyGrid,
+    /* Negativo da energia, em unidades inteiras do modelo (veja `EnergyModel::scale`) */
+    score: i32,
+    /* Vizinhos da rede, consultados a cada contato */
+    neighbour_offsets: &'static [Point],
+    evaluations: Cell<u64> /* Quantidade de avaliações de energia (completas ou incrementais) */
 }
 
 impl Conformation {
     pub fn new(protein: &Protein, config: ACOConfig) -> Self {
+        let lattice = config.lattice.get();
+        let mut grid = OccupancyGrid::new(protein.len(), lattice);
+
+        // Marca aminoácidos fixos
+        grid.set((0, 0, 0), 0);
+        grid.set(lattice.first_bond(), 1);
+
         Self {
             protein: protein.clone(),
             conformation: vec![None; protein.len() - 2],
             config,
-            i: 0
+            i: 0,
+            lo: 0,
+            history: Vec::new(),
+            started: false,
+            failed_at: 0,
+            failures: 0,
+            tabu: match config.backtracking {
+                Backtracking::Halving => Vec::new(),
+                _ => vec![0; protein.len() - 1]
+            },
+            backtrack_from: 0,
+            stats: Cell::new(ConstructionStats::default()),
+            positions: vec![(0, 0, 0), lattice.first_bond()],
+            frames: vec![Frame::IDENTITY; 2],
+            grid,
+            score: 0,
+            neighbour_offsets: lattice.neighbour_offsets(),
+            evaluations: Cell::new(0)
         }
     }
 
+    pub fn config(&self) -> ACOConfig {
+        self.config
+    }
+
+    pub fn lattice(&self) -> &'static dyn Lattice {
+        self.config.lattice.get()
+    }
+
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations.get()
+    }
+
+    fn count_evaluation(&self) {
+        self.evaluations.set(self.evaluations.get() + 1);
+    }
+
     pub fn get(&self, i: usize) -> Option<Direction> {
         self.conformation[i]
     }
 
+    pub fn directions(&self) -> &[Option<Direction>] {
+        &self.conformation
+    }
+
+    /* Quantidade de direções já escolhidas */
+    pub fn grown(&self) -> usize {
+        self.i
+    }
+
     pub fn is_fully_grown(&self) -> bool {
         self.i == self.conformation.len()
     }
 
-    pub fn grow(&mut self, pheromones: &Pheromones, rng: &mut ThreadRng) -> bool {
+    pub fn grow<R: Rng + ?Sized>(&mut self, pheromones: &Pheromones, rng: &mut R) -> bool {
+        if self.config.construction == Construction::Bidirectional {
+            return self.grow_bidirectional(pheromones, rng);
+        }
+
         if self.is_fully_grown() {
             return true;
         }
 
-        let original_direction = self.conformation[self.i]; /* Salva direção para resetar em caso de erro */
-        
-        let fitness = self.eval();
+        let k = self.i + 2; /* Aminoácido a ser colocado */
+        let last = self.positions[k - 1];
+        let frame = self.frames[k - 1];
+        let lattice = self.lattice();
 
-        let valid_directions: Vec<Direction> = Direction::iter()
-            .filter(|d| {
-                self.conformation[self.i] = Some(*d);
-                self.is_valid()
+        let candidates: Vec<(Direction, Frame, Point)> = lattice.directions().into_iter()
+            .map(|d| {
+                let new_frame = lattice.turn(&frame, d);
+                (d, new_frame, add(last, lattice.heading(&new_frame)))
             })
+            .filter(|&(d, _, pos)| !self.is_tabu(Side::Forward, d) && self.can_place(k, pos))
             .collect();
 
-        if valid_directions.is_empty() {
-            self.conformation[self.i] = original_direction;
+        let slot = self.i;
+        let Some((d, frame, pos)) = self.choose(k, k, &candidates, |d, h| pheromones.get_weight(slot, d, h), pheromones, rng) else {
             return false;
+        };
+
+        self.place(d, frame, pos);
+
+        true
+    }
+
+    /* Sorteia um dos candidatos para o aminoácido `k`, com peso dado pelos feromônios e pelos
+    contatos com aminoácidos de índice menor que `limit` */
+    fn choose<R: Rng + ?Sized>(
+        &self,
+        k: usize,
+        limit: usize,
+        candidates: &[(Direction, Frame, Point)],
+        weight: impl Fn(Direction, f64) -> f64,
+        pheromones: &Pheromones,
+        rng: &mut R
+    ) -> Option<(Direction, Frame, Point)> {
+        if candidates.is_empty() {
+            return None;
         }
 
-        let weights: Vec<f64> = valid_directions.iter().map(|&d| {
-                self.conformation[self.i] = Some(d);
-                let new_fitness = self.eval();
-                let h = new_fitness - fitness + 1.0;
+        let scale = self.protein.model().scale();
+        let weights: Vec<f64> = candidates.iter().map(|&(d, _, pos)| {
+                self.count_evaluation();
+                /* No modelo HP é a quantidade de contatos H-H mais 1 */
+                let h = (1.0 + self.score_at(k, pos, limit) as f64 / scale).max(MIN_HEURISTIC);
 
-                pheromones.get_weight(self.i, d, h)
+                weight(d, h)
             })
             .collect();
-                
-        self.conformation[self.i] = if weights.iter().sum::<f64>() == 0.0 {
-            Some(*valid_directions.choose(rng).unwrap())
+
+        /* No ACS a formiga às vezes segue direto a direção de maior peso */
+        let greedy = pheromones.greedy_probability().is_some_and(|q0| rng.random::<f64>() < q0);
+
+        let chosen = if greedy {
+            let best = (0..weights.len()).max_by(|&a, &b| weights[a].total_cmp(&weights[b]).then(b.cmp(&a))).unwrap();
+            candidates[best]
+        } else if weights.iter().sum::<f64>() == 0.0 {
+            *candidates.choose(rng).unwrap()
         } else {
             let dist = WeightedIndex::new(&weights).unwrap();
-            Some(valid_directions[dist.sample(rng)])
+            candidates[dist.sample(rng)]
         };
 
+        Some(chosen)
+    }
+
+    /* Coloca o próximo aminoácido em `pos`, já validada */
+    fn place(&mut self, d: Direction, frame: Frame, pos: Point) {
+        let k = self.i + 2;
+
+        self.score += self.score_at(k, pos, k);
+        self.grid.set(pos, k);
+        self.positions.push(pos);
+        self.frames.push(frame);
+        self.conformation[self.i] = Some(d);
+
         self.i += 1;
+    }
 
-        true
+    /* Uma posição é válida se está livre e não prende a cadeia (veja `Lookahead`) */
+    fn can_place(&self, k: usize, pos: Point) -> bool {
+        self.grid.is_free(pos) && self.has_room(k, pos)
     }
 
-    pub fn rewind(&mut self) {
-        self.i /= 2;
+    /* Negativo da energia dos contatos que o aminoácido `k` faz em `pos` com aminoácidos de
+    índice menor que `limit`. No modelo HP é a quantidade de contatos H-H. */
+    fn score_at(&self, k: usize, pos: Point, limit: usize) -> i32 {
+        let model = self.protein.model();
+        let aa = self.protein[k];
 
-        for i in self.i..self.conformation.len() {
-            if self.conformation[i].is_none() {
-                break;
-            }
-            self.conformation[i] = None;
+        if !model.interacts(aa) {
+            return 0;
         }
-    }
-
-    pub fn is_valid(&self) -> bool {
-        let mut filled = HashSet::from([(0, 0), (1, 0)]);
-        
-        let mut pos = (1, 0);
-        let mut v = (1, 0);
 
-        let n = self.conformation.len();
+        self.neighbour_offsets.iter()
+            .filter_map(|&offset| self.grid.get(add(pos, offset)))
+            .filter(|&j| j < limit && j.abs_diff(k) > 1)
+            .map(|j| -model.interaction(aa, self.protein[j]))
+            .sum()
+    }
 
-        for (i, direction) in self.conformation.iter().enumerate() {
-            if let Some(direction) = direction {
-                v = Conformation::get_new_velocity(v, *direction);
-            }
+    /// Variação de `score` ao trocar a direção `i` por `d`, o que gira rigidamente o resto da
+    /// cadeia em torno do aminoácido `i + 1`. Retorna `None` se o giro gerar sobreposição.
+    /// Só faz sentido com a conformação completa.
+    pub fn pivot_delta(&self, i: usize, d: Direction) -> Option<i32> {
+        self.count_evaluation();
 
-            pos.0 += v.0;
-            pos.1 += v.1;
+        let t = i + 2; /* Primeiro aminoácido que se move */
+        let (pivot, rotation) = self.pivot_rotation(i, d);
 
-            let is_dead_end = i > 0 && i < n && [(1, 0), (-1, 0), (0, 1), (0, -1)].iter()
-                .all(|offset| {
-                    let new_pos = (pos.0 + offset.0, pos.1 + offset.1);
-                    filled.contains(&new_pos)
-                });
+        let mut delta = 0;
+        for j in t..self.positions.len() {
+            let old_pos = self.positions[j];
+            let new_pos = rotation.rotate_around(old_pos, pivot);
 
-            if filled.contains(&pos) || is_dead_end {
-                return false;
+            if self.grid.get(new_pos).is_some_and(|o| o < t) {
+                return None;
             }
 
-            filled.insert(pos);
+            delta += self.score_at(j, new_pos, t) - self.score_at(j, old_pos, t);
         }
-        
-        true
+
+        Some(delta)
     }
 
-    pub fn eval(&self) -> f64 {
-        let mut fold = HashMap::new();
-        
-        // Marca aminoácidos fixos
-        fold.insert((0, 0), 0);
-        fold.insert((1, 0), 1);
-        
-        let mut pos = (1, 0); /* Inicializa posição do agente */
-        let mut v = (1, 0);   /* Inicializa velocidade do agente */
-
-        for (i, direction) in self.conformation.iter().enumerate() {
-            if let Some(direction) = direction {
-                v = Conformation::get_new_velocity(v, *direction); /* Calcula nova velocidade */
-                pos = (pos.0 + v.0, pos.1 + v.1); /* Aplica nova velocidade */
-    
-                fold.insert(pos, i + 2); /* Marca posição do aminoácido */
-            }
+    /* Aplica um giro já avaliado por `pivot_delta` */
+    pub fn apply_pivot(&mut self, i: usize, d: Direction, delta: i32) {
+        let t = i + 2;
+        let (pivot, rotation) = self.pivot_rotation(i, d);
+
+        for &pos in &self.positions[t..] {
+            self.grid.clear(pos);
         }
 
-        let mut hh_count = 0;
-        for (&pos, &i) in fold.iter() {
-            if self.protein[i] == AminoAcid::Hydrophobic {
-                let neighbour_offsets = [(1, 0), (-1, 0), (0, 1), (0, -1)];
-
-                for offset in neighbour_offsets {
-                    let neighbour_pos = (pos.0 + offset.0, pos.1 + offset.1);
-                    
-                    if let Some(&neighbour_idx) = fold.get(&neighbour_pos) {
-                        if self.protein[neighbour_idx] == AminoAcid::Hydrophobic
-                        && (neighbour_idx as isize - i as isize).abs() > 1 {
-                            hh_count += 1;
-                        }
-                    }
-                }
-            }
+        for j in t..self.positions.len() {
+            let new_pos = rotation.rotate_around(self.positions[j], pivot);
+            self.positions[j] = new_pos;
+            self.frames[j] = rotation.then(&self.frames[j]);
+            self.grid.set(new_pos, j);
         }
 
-        (hh_count / 2) as f64
+        self.conformation[i] = Some(d);
+        self.score += delta;
     }
 
-    fn get_new_velocity(v: (i32, i32), direction: Direction) -> (i32, i32) {
-        match direction {
-            Direction::Left => match v {
-                (1,  0) => (0,  1),
-                (-1, 0) => (0, -1),
-                (0,  1) => (-1, 0),
-                (0, -1) => (1,  0),
-                _ => panic!("Velocidade inválida"),
-            },
-            Direction::Right => match v {
-                (1,  0) => (0, -1),
-                (-1, 0) => (0,  1),
-                (0,  1) => (1,  0),
-                (0, -1) => (-1, 0),
-                _ => panic!("Velocidade inválida"),
-            },
-            _ => v,
-        }
+    /* Posição do pivô e rotação que leva a orientação atual à nova */
+    fn pivot_rotation(&self, i: usize, d: Direction) -> (Point, Frame) {
+        let pivot = self.positions[i + 1];
+        let new_frame = self.lattice().turn(&self.frames[i + 1], d);
+
+        (pivot, new_frame.then(&self.frames[i + 2].inverse()))
     }
 
-    pub fn to_string(&self) -> String {
-        self.conformation.iter()
-            .map(|dir| dir.unwrap().char())
-            .collect()
+    /* Verifica do zero se as coordenadas formam um caminho sem sobreposição */
+    pub fn is_valid(&self) -> bool {
+        let mut filled = HashSet::new();
+
+        self.positions.iter().all(|&pos| filled.insert(pos))
+            && self.positions.windows(2).all(|pair| self.lattice().are_adjacent(pair[0], pair[1]))
     }
-}
 
+    /// Negativo da energia da conformação; no modelo HP, a quantidade de contatos H-H.
+    pub fn eval(&self) -> f64 {
+        self.score as f64 / self.protein.model().scale()
+    }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum Direction {
-    Straight,
-    Left,
-    Right
+    pub fn energy(&self) -> Energy {
+        Energy::from_fitness(self.eval())
+    }
 }
 
-impl Direction {
-    pub fn as_index(&self) -> usize {
-        match self {
-            Direction::Left => 0,
-            Direction::Straight => 1,
-            Direction::Right => 2
-        }
+/* Uma conformação incompleta (como a melhor de uma execução sem nenhuma iteração) mostra só as
+   direções já escolhidas */
+impl fmt::Display for Conformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_partial_string())
     }
+}
 
-    pub fn iter() -> impl Iterator<Item = Direction> {
-        [Direction::Straight, Direction::Left, Direction::Right].into_iter()
-    }
+/// Direção relativa, como índice nas direções da rede (veja `Lattice::turns`). O índice também
+/// é a coluna da matriz de feromônios.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Direction(u8);
 
-    pub fn vec() -> Vec<Direction> {
-        Self::iter().collect()
+impl Direction {
+    pub fn from_index(i: usize) -> Self {
+        Direction(i as u8)
     }
 
-    pub fn char(&self) -> char {
-        match self {
-            Direction::Straight => 'S',
-            Direction::Left => 'L',
-            Direction::Right => 'R'
-        }
+    pub fn as_index(&self) -> usize {
+        self.0 as usize
     }
-}
\ No newline at end of file
+}
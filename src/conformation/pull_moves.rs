@@ -0,0 +1,184 @@
+use crate::conformation::{lattice::{add, sub, Point}, Conformation, Direction};
+
+/* Aminoácidos movidos por um pull move, com suas novas posições */
+pub type PullMove = Vec<(usize, Point)>;
+
+/* Como desfazer um pull move já aplicado */
+pub enum PullUndo {
+    Moves(PullMove),
+    /* Movimentos que tiram os aminoácidos fixos do lugar exigem reconstruir a conformação */
+    Snapshot(Box<Conformation>)
+}
+
+impl Conformation {
+    fn neighbours(&self, pos: Point) -> impl Iterator<Item = Point> + use<> {
+        self.neighbour_offsets.iter().map(move |&offset| add(pos, offset))
+    }
+
+    /// Pull moves (Lesh, Mitzenmacher e Whitesides, 2003) do aminoácido `i`, nos dois sentidos
+    /// da cadeia. Cada movimento leva `i` para uma posição livre vizinha do aminoácido ao lado,
+    /// formando um paralelogramo com ele, e arrasta os aminoácidos seguintes só até a cadeia
+    /// voltar a ficar conectada. Na rede quadrada são os movimentos pela diagonal originais.
+    /// Só faz sentido com a conformação completa.
+    pub fn pull_moves(&self, i: usize) -> Vec<PullMove> {
+        let mut moves = Vec::new();
+
+        for step in [-1, 1] {
+            /* Aminoácido que fica parado ao lado de `i`; os do lado `step` são arrastados */
+            match self.residue(i as isize - step) {
+                Some(anchor) => self.internal_pulls(i, anchor, step, &mut moves),
+                None => self.end_pulls(i, step, &mut moves)
+            }
+        }
+
+        moves
+    }
+
+    fn residue(&self, k: isize) -> Option<usize> {
+        (k >= 0 && (k as usize) < self.positions.len()).then_some(k as usize)
+    }
+
+    fn internal_pulls(&self, i: usize, anchor: usize, step: isize, moves: &mut Vec<PullMove>) {
+        let pos = self.positions[i];
+        let anchor_pos = self.positions[anchor];
+        let opposite = sub(add(anchor_pos, anchor_pos), pos);
+
+        for l in self.neighbours(anchor_pos).filter(|&l| l != pos && l != opposite && self.grid.is_free(l)) {
+            let c = add(pos, sub(l, anchor_pos));
+
+            let pull = match self.residue(i as isize + step) {
+                Some(next) if self.positions[next] == c => vec![(i, l)],
+                Some(next) if self.grid.is_free(c) => self.drag(vec![(i, l), (next, c)], step),
+                Some(_) => continue,
+                None => vec![(i, l)]
+            };
+
+            moves.push(pull);
+        }
+    }
+
+    /* Extremidade sem vizinho do lado oposto: anda duas casas por posições livres */
+    fn end_pulls(&self, i: usize, step: isize, moves: &mut Vec<PullMove>) {
+        let Some(next) = self.residue(i as isize + step) else {
+            return;
+        };
+
+        let pos = self.positions[i];
+
+        for c in self.neighbours(pos).filter(|&c| self.grid.is_free(c)) {
+            for l in self.neighbours(c).filter(|&l| l != pos && self.grid.is_free(l)) {
+                moves.push(self.drag(vec![(i, l), (next, c)], step));
+            }
+        }
+    }
+
+    /* Cada aminoácido seguinte ocupa a posição antiga de quem está duas casas à frente dele */
+    fn drag(&self, mut pull: PullMove, step: isize) -> PullMove {
+        let mut j = pull[1].0 as isize + step;
+
+        while let Some(k) = self.residue(j) {
+            let previous = pull.last().unwrap().1;
+
+            if self.lattice().are_adjacent(self.positions[k], previous) {
+                break;
+            }
+
+            pull.push((k, self.positions[(j - 2 * step) as usize]));
+            j += step;
+        }
+
+        pull
+    }
+
+    /// Aplica um pull move gerado por `pull_moves` e retorna a variação de `score`
+    /// e como desfazê-lo.
+    pub fn apply_pull(&mut self, pull: &PullMove) -> (i32, PullUndo) {
+        self.count_evaluation();
+
+        if pull.iter().any(|&(k, _)| k < 2) {
+            return self.apply_pull_rebuilding(pull);
+        }
+
+        let before = self.score;
+        let undo = pull.iter().map(|&(k, _)| (k, self.positions[k])).collect();
+
+        self.move_residues(pull);
+
+        (self.score - before, PullUndo::Moves(undo))
+    }
+
+    pub fn undo_pull(&mut self, undo: PullUndo) {
+        match undo {
+            PullUndo::Moves(moves) => self.move_residues(&moves),
+            PullUndo::Snapshot(conformation) => *self = *conformation
+        }
+    }
+
+    /* Move aminoácidos mantendo os aminoácidos fixos no lugar */
+    fn move_residues(&mut self, moves: &[(usize, Point)]) {
+        /* Retira todos antes de recolocar, assim cada contato é contado uma única vez */
+        for &(k, _) in moves {
+            let old_pos = self.positions[k];
+            self.grid.clear(old_pos);
+            self.score -= self.score_at(k, old_pos, usize::MAX);
+        }
+
+        for &(k, new_pos) in moves {
+            self.score += self.score_at(k, new_pos, usize::MAX);
+            self.grid.set(new_pos, k);
+            self.positions[k] = new_pos;
+        }
+
+        let first = moves.iter().map(|&(k, _)| k).min().unwrap();
+        let last = moves.iter().map(|&(k, _)| k).max().unwrap();
+
+        /* Na rede cúbica, mudar a orientação de um aminoácido muda a codificação relativa de
+        todos os seguintes; o recálculo para quando a orientação volta a ser a antiga */
+        let lattice = self.lattice();
+
+        for k in first.max(2)..self.positions.len() {
+            let frame = self.frames[k - 1];
+            let d = lattice.direction_to(&frame, sub(self.positions[k], self.positions[k - 1]))
+                .expect("aminoácidos consecutivos devem ser vizinhos");
+            let new_frame = lattice.turn(&frame, d);
+
+            self.conformation[k - 2] = Some(d);
+
+            if k > last + 1 && new_frame == self.frames[k] {
+                break;
+            }
+
+            self.frames[k] = new_frame;
+        }
+    }
+
+    fn apply_pull_rebuilding(&mut self, pull: &PullMove) -> (i32, PullUndo) {
+        let mut positions = self.positions.clone();
+        for &(k, new_pos) in pull {
+            positions[k] = new_pos;
+        }
+
+        /* A primeira ligação passa a ser a referência, mantendo a orientação sempre que possível */
+        let lattice = self.lattice();
+        let mut frame = lattice.frame_towards(sub(positions[1], positions[0]), &self.frames[1])
+            .expect("aminoácidos consecutivos devem ser vizinhos");
+
+        let directions: Vec<Direction> = positions.windows(2)
+            .skip(1)
+            .map(|pair| {
+                let d = lattice.direction_to(&frame, sub(pair[1], pair[0])).expect("aminoácidos consecutivos devem ser vizinhos");
+                frame = lattice.turn(&frame, d);
+                d
+            })
+            .collect();
+
+        let rebuilt = Conformation::from_directions(&self.protein, self.config, &directions)
+            .expect("pull moves sempre geram conformações válidas");
+        rebuilt.evaluations.set(self.evaluations.get());
+
+        let delta = rebuilt.score - self.score;
+        let snapshot = std::mem::replace(self, rebuilt);
+
+        (delta, PullUndo::Snapshot(Box::new(snapshot)))
+    }
+}
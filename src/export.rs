@@ -0,0 +1,485 @@
+use std::{error::Error, fmt, fs::{self, OpenOptions}, io::{self, Write}, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{aco::{config::ACOConfig, report::{IterationStats, RunReport}, stop::{StopCriteria, StopReason}}, conformation::{backtracking::DEFAULT_BACKTRACK_DEPTH, lattice::LatticeKind, local_search::Neighbourhood, Backtracking, Construction, ConstructionStats, Lookahead}, pheromones::{PheromoneUpdate, UpdateParams}, protein::{protein_to_string, Energy, EnergyModel, Protein}};
+
+/* Incrementar sempre que um campo de `RunRecord` mudar de uma versão publicada para a próxima.
+   Campos novos de uma mesma versão têm `#[serde(default)]`, para ler os registros gravados antes deles */
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Resultado de uma execução do ACO, no formato gravado em JSON Lines e CSV.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub schema_version: u32,
+    pub benchmark: Option<String>,
+    pub benchmark_index: Option<usize>,
+    pub sequence: String,
+    /* Nome do modelo de energia (`EnergyModel::name`) */
+    #[serde(default = "default_energy_model")]
+    pub energy_model: String,
+    pub config: ACOConfig,
+    pub conformation: String,
+    pub energy: Energy,
+    /* Erro relativo ao ótimo conhecido (`config.optimum`), veja `Energy::relative_error_to_optimum` */
+    #[serde(default)]
+    pub relative_error: Option<f64>,
+    pub wall_time_secs: f64,
+    pub best_iteration: Option<u16>,
+    #[serde(default)]
+    pub evaluations: u64,
+    #[serde(default = "default_stop_reason")]
+    pub stop_reason: StopReason,
+    pub best_trace: Vec<Energy>,
+    /* Somadas de todas as formigas, veja `ConstructionStats` */
+    #[serde(default)]
+    pub construction_stats: ConstructionStats
+}
+
+/* Registros gravados antes do campo existir eram sempre do modelo HP */
+fn default_energy_model() -> String {
+    EnergyModel::hp().name().to_string()
+}
+
+/* Antes dos critérios de parada, toda execução ia até `max_iter` */
+fn default_stop_reason() -> StopReason {
+    StopReason::MaxIterations
+}
+
+impl RunRecord {
+    pub fn new(protein: &Protein, config: ACOConfig, report: &RunReport) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            benchmark: None,
+            benchmark_index: None,
+            sequence: protein_to_string(protein.residues()),
+            energy_model: protein.model().name().to_string(),
+            config,
+            conformation: report.best_conformation.to_string(),
+            energy: report.best,
+            relative_error: config.optimum.and_then(|optimum| report.best.relative_error_to_optimum(optimum)),
+            wall_time_secs: report.elapsed.as_secs_f64(),
+            best_iteration: report.best_iteration,
+            evaluations: report.evaluations,
+            stop_reason: report.stop_reason,
+            best_trace: report.best_trace(),
+            construction_stats: report.construction_stats()
+        }
+    }
+
+    pub fn with_benchmark(mut self, name: Option<String>, index: usize) -> Self {
+        self.benchmark = name;
+        self.benchmark_index = Some(index);
+        self
+    }
+}
+
+/* CSV não suporta campos aninhados: a configuração vira colunas e o histórico uma lista separada por ';' */
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    schema_version: u32,
+    benchmark: Option<String>,
+    benchmark_index: Option<usize>,
+    sequence: String,
+    #[serde(default = "default_energy_model")]
+    energy_model: String,
+    ant_count: u16,
+    max_iter: u16,
+    no_impr_max: u16,
+    evaporation: f64,
+    alpha: f64,
+    beta: f64,
+    neutral_mutation_rate: f64,
+    seed: u64,
+    /* Como em `ACOConfig`, as colunas que vieram depois têm valor padrão */
+    #[serde(default)]
+    target: Option<Energy>,
+    #[serde(default)]
+    time_limit_secs: Option<f64>,
+    #[serde(default)]
+    max_evaluations: Option<u64>,
+    #[serde(default)]
+    max_stagnation: Option<u16>,
+    #[serde(default)]
+    min_entropy: Option<f64>,
+    #[serde(default)]
+    neighbourhood: Neighbourhood,
+    #[serde(default)]
+    lattice: LatticeKind,
+    #[serde(default)]
+    pheromone_update: PheromoneUpdate,
+    #[serde(default = "default_elitist_weight")]
+    elitist_weight: f64,
+    #[serde(default = "default_rank_count")]
+    rank_count: u16,
+    #[serde(default = "default_p_best")]
+    p_best: f64,
+    #[serde(default = "default_reinit_stagnation")]
+    reinit_stagnation: u16,
+    #[serde(default = "default_local_evaporation")]
+    local_evaporation: f64,
+    #[serde(default = "default_q0")]
+    q0: f64,
+    #[serde(default)]
+    construction: Construction,
+    #[serde(default)]
+    backtracking: Backtracking,
+    #[serde(default = "default_backtrack_depth")]
+    backtrack_depth: u16,
+    #[serde(default)]
+    lookahead: Lookahead,
+    #[serde(default)]
+    optimum: Option<Energy>,
+    conformation: String,
+    energy: Energy,
+    #[serde(default)]
+    relative_error: Option<f64>,
+    wall_time_secs: f64,
+    best_iteration: Option<u16>,
+    #[serde(default)]
+    evaluations: u64,
+    #[serde(default = "default_stop_reason")]
+    stop_reason: StopReason,
+    best_trace: String,
+    #[serde(default)]
+    backtracks: u32,
+    #[serde(default)]
+    undone_steps: u32,
+    #[serde(default)]
+    lookahead_rejections: u32
+}
+
+fn default_elitist_weight() -> f64 {
+    UpdateParams::default().elitist_weight
+}
+
+fn default_rank_count() -> u16 {
+    UpdateParams::default().rank_count
+}
+
+fn default_p_best() -> f64 {
+    UpdateParams::default().p_best
+}
+
+fn default_reinit_stagnation() -> u16 {
+    UpdateParams::default().reinit_stagnation
+}
+
+fn default_local_evaporation() -> f64 {
+    UpdateParams::default().local_evaporation
+}
+
+fn default_q0() -> f64 {
+    UpdateParams::default().q0
+}
+
+fn default_backtrack_depth() -> u16 {
+    DEFAULT_BACKTRACK_DEPTH
+}
+
+impl From<&RunRecord> for CsvRow {
+    fn from(record: &RunRecord) -> Self {
+        let config = record.config;
+
+        Self {
+            schema_version: record.schema_version,
+            benchmark: record.benchmark.clone(),
+            benchmark_index: record.benchmark_index,
+            sequence: record.sequence.clone(),
+            energy_model: record.energy_model.clone(),
+            ant_count: config.ant_count,
+            max_iter: config.max_iter,
+            no_impr_max: config.no_impr_max,
+            evaporation: config.evaporation,
+            alpha: config.alpha,
+            beta: config.beta,
+            neutral_mutation_rate: config.neutral_mutation_rate,
+            seed: config.seed,
+            target: config.stop.target,
+            time_limit_secs: config.stop.time_limit_secs,
+            max_evaluations: config.stop.max_evaluations,
+            max_stagnation: config.stop.max_stagnation,
+            min_entropy: config.stop.min_entropy,
+            neighbourhood: config.neighbourhood,
+            lattice: config.lattice,
+            pheromone_update: config.pheromone_update,
+            elitist_weight: config.update_params.elitist_weight,
+            rank_count: config.update_params.rank_count,
+            p_best: config.update_params.p_best,
+            reinit_stagnation: config.update_params.reinit_stagnation,
+            local_evaporation: config.update_params.local_evaporation,
+            q0: config.update_params.q0,
+            construction: config.construction,
+            backtracking: config.backtracking,
+            backtrack_depth: config.backtrack_depth,
+            lookahead: config.lookahead,
+            optimum: config.optimum,
+            conformation: record.conformation.clone(),
+            energy: record.energy,
+            relative_error: record.relative_error,
+            wall_time_secs: record.wall_time_secs,
+            best_iteration: record.best_iteration,
+            evaluations: record.evaluations,
+            stop_reason: record.stop_reason,
+            best_trace: record.best_trace.iter()
+                .map(|energy| energy.to_string())
+                .collect::<Vec<_>>()
+                .join(";"),
+            backtracks: record.construction_stats.backtracks,
+            undone_steps: record.construction_stats.undone_steps,
+            lookahead_rejections: record.construction_stats.lookahead_rejections
+        }
+    }
+}
+
+impl TryFrom<CsvRow> for RunRecord {
+    type Error = ExportError;
+
+    fn try_from(row: CsvRow) -> Result<Self, ExportError> {
+        let best_trace = row.best_trace.split(';')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().map(Energy::new).map_err(|_| ExportError::InvalidTrace(row.best_trace.clone())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            schema_version: row.schema_version,
+            benchmark: row.benchmark,
+            benchmark_index: row.benchmark_index,
+            sequence: row.sequence,
+            energy_model: row.energy_model,
+            config: ACOConfig {
+                ant_count: row.ant_count,
+                max_iter: row.max_iter,
+                no_impr_max: row.no_impr_max,
+                evaporation: row.evaporation,
+                alpha: row.alpha,
+                beta: row.beta,
+                neutral_mutation_rate: row.neutral_mutation_rate,
+                seed: row.seed,
+                stop: StopCriteria {
+                    target: row.target,
+                    time_limit_secs: row.time_limit_secs,
+                    max_evaluations: row.max_evaluations,
+                    max_stagnation: row.max_stagnation,
+                    min_entropy: row.min_entropy
+                },
+                neighbourhood: row.neighbourhood,
+                lattice: row.lattice,
+                pheromone_update: row.pheromone_update,
+                update_params: UpdateParams {
+                    elitist_weight: row.elitist_weight,
+                    rank_count: row.rank_count,
+                    p_best: row.p_best,
+                    reinit_stagnation: row.reinit_stagnation,
+                    local_evaporation: row.local_evaporation,
+                    q0: row.q0
+                },
+                construction: row.construction,
+                backtracking: row.backtracking,
+                backtrack_depth: row.backtrack_depth,
+                lookahead: row.lookahead,
+                optimum: row.optimum
+            },
+            conformation: row.conformation,
+            energy: row.energy,
+            relative_error: row.relative_error,
+            wall_time_secs: row.wall_time_secs,
+            best_iteration: row.best_iteration,
+            evaluations: row.evaluations,
+            stop_reason: row.stop_reason,
+            best_trace,
+            construction_stats: ConstructionStats {
+                backtracks: row.backtracks,
+                undone_steps: row.undone_steps,
+                lookahead_rejections: row.lookahead_rejections
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    JsonLines,
+    Csv
+}
+
+impl ExportFormat {
+    pub fn append(&self, path: &Path, record: &RunRecord) -> Result<(), ExportError> {
+        match self {
+            ExportFormat::JsonLines => append_jsonl(path, record),
+            ExportFormat::Csv => append_csv(path, record)
+        }
+    }
+
+    pub fn read(&self, path: &Path) -> Result<Vec<RunRecord>, ExportError> {
+        match self {
+            ExportFormat::JsonLines => read_jsonl(path),
+            ExportFormat::Csv => read_csv(path)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    JsonLine { line: usize, error: serde_json::Error },
+    Csv(csv::Error),
+    InvalidTrace(String),
+    /* Registro gravado por outra versão do formato (veja `SCHEMA_VERSION`) */
+    SchemaVersion { line: usize, found: u32, expected: u32 },
+    /* O CSV existente tem outras colunas, então o registro novo não pode ser acrescentado nele */
+    CsvHeader
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(e) => write!(f, "erro de entrada/saída: {}", e),
+            ExportError::Json(e) => write!(f, "erro ao gerar JSON: {}", e),
+            ExportError::JsonLine { line, error } => write!(f, "linha {}: JSON inválido: {}", line, error),
+            ExportError::Csv(e) => write!(f, "CSV inválido: {}", e),
+            ExportError::InvalidTrace(trace) => write!(f, "histórico inválido: '{}'", trace),
+            ExportError::SchemaVersion { line, found, expected } => {
+                write!(f, "linha {}: versão {} do formato não é suportada (esperada: {})", line, found, expected)
+            }
+            ExportError::CsvHeader => write!(f, "o arquivo CSV tem colunas de outra versão do formato; use outro arquivo")
+        }
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::Io(e) => Some(e),
+            ExportError::Json(e) => Some(e),
+            ExportError::JsonLine { error, .. } => Some(error),
+            ExportError::Csv(e) => Some(e),
+            ExportError::InvalidTrace(_) | ExportError::SchemaVersion { .. } | ExportError::CsvHeader => None
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+impl From<csv::Error> for ExportError {
+    fn from(e: csv::Error) -> Self {
+        ExportError::Csv(e)
+    }
+}
+
+pub fn append_jsonl(path: &Path, record: &RunRecord) -> Result<(), ExportError> {
+    let mut line = serde_json::to_string(record).map_err(ExportError::Json)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+
+    Ok(())
+}
+
+/* Só a versão, lida antes do resto para um registro de outra versão não virar um erro de JSON */
+#[derive(Deserialize)]
+struct SchemaVersion {
+    schema_version: u32
+}
+
+fn check_version(line: usize, found: u32) -> Result<(), ExportError> {
+    if found != SCHEMA_VERSION {
+        return Err(ExportError::SchemaVersion { line, found, expected: SCHEMA_VERSION });
+    }
+
+    Ok(())
+}
+
+pub fn read_jsonl(path: &Path) -> Result<Vec<RunRecord>, ExportError> {
+    let content = fs::read_to_string(path)?;
+
+    content.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let json_error = |error| ExportError::JsonLine { line: i + 1, error };
+
+            let version: SchemaVersion = serde_json::from_str(line).map_err(json_error)?;
+            check_version(i + 1, version.schema_version)?;
+
+            serde_json::from_str(line).map_err(json_error)
+        })
+        .collect()
+}
+
+pub fn append_csv(path: &Path, record: &RunRecord) -> Result<(), ExportError> {
+    let row = CsvRow::from(record);
+
+    /* O cabeçalho só é escrito quando o arquivo ainda está vazio; senão, ele precisa ser o desta versão */
+    let is_empty = fs::metadata(path).map(|m| m.len() == 0).unwrap_or(true);
+
+    if !is_empty && csv::Reader::from_path(path)?.headers()? != &csv_header(&row)? {
+        return Err(ExportError::CsvHeader);
+    }
+
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(is_empty)
+        .from_writer(file);
+
+    writer.serialize(row)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/* Cabeçalho que `CsvRow` gera, serializando `row` em memória */
+fn csv_header(row: &CsvRow) -> Result<csv::StringRecord, ExportError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.serialize(row)?;
+
+    let data = writer.into_inner().map_err(|e| ExportError::Io(e.into_error()))?;
+    Ok(csv::Reader::from_reader(data.as_slice()).headers()?.clone())
+}
+
+pub fn read_csv(path: &Path) -> Result<Vec<RunRecord>, ExportError> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let version_column = headers.iter().position(|name| name == "schema_version");
+
+    reader.records()
+        .map(|record| {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line() as usize);
+
+            /* Sem a coluna, a desserialização abaixo já reporta o erro */
+            if let Some(version) = version_column.and_then(|i| record.get(i)).and_then(|v| v.parse().ok()) {
+                check_version(line, version)?;
+            }
+
+            RunRecord::try_from(record.deserialize::<CsvRow>(Some(&headers))?)
+        })
+        .collect()
+}
+
+/* Uma linha por iteração, para gerar curvas de convergência */
+pub fn write_iterations_csv(path: &Path, iterations: &[IterationStats]) -> Result<(), ExportError> {
+    let mut writer = csv::Writer::from_path(path)?;
+
+    for stats in iterations {
+        writer.serialize(stats)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+pub fn read_iterations_csv(path: &Path) -> Result<Vec<IterationStats>, ExportError> {
+    let mut reader = csv::Reader::from_path(path)?;
+
+    reader.deserialize()
+        .map(|stats| Ok(stats?))
+        .collect()
+}
@@ -0,0 +1,13 @@
+pub mod aco;
+pub mod conformation;
+pub mod export;
+pub mod pheromones;
+pub mod protein;
+
+#[cfg(feature = "cli")]
+pub mod cli;
+
+pub use aco::{aco_protein_folding_2dhp, aco_protein_folding_2dhp_from, async_aco::{async_aco_protein_folding_2dhp, async_aco_protein_folding_2dhp_from}, checkpoint::{aco_protein_folding_2dhp_checkpointed, resume_from, resume_from_with, run_checkpointed, Checkpoint}, config::ACOConfig, engine::{AcoEngine, IterationSnapshot}, report::RunReport, stop::CancellationToken, WarmStart};
+pub use conformation::{coordinates::AbsoluteDirection, lattice::{Lattice, LatticeKind}, Conformation, Direction};
+pub use pheromones::{PheromoneSnapshot, Pheromones};
+pub use protein::{AminoAcid, Energy, EnergyModel, Protein};
@@ -0,0 +1,163 @@
+use crate::{aco::config::ACOConfig, conformation::{Conformation, Construction, Direction}, protein::Protein};
+
+pub mod snapshot;
+pub mod update;
+
+pub use snapshot::{PheromoneSnapshot, SnapshotError};
+pub use update::{PheromoneUpdate, UpdateParams};
+
+/* Valor inicial de todos os feromônios, também o alvo da atualização local do ACS */
+const INITIAL_PHEROMONE: f64 = 0.3;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pheromones {
+    pheromones: Vec<Vec<f64>>,
+    /* Matriz da leitura inversa, do último aminoácido ao primeiro, só na construção bidirecional */
+    backward: Vec<Vec<f64>>,
+    /* Direção da leitura inversa correspondente a cada direção (veja `Lattice::reverse_directions`) */
+    reverse: Vec<Direction>,
+    config: ACOConfig,
+    /* Aminoácidos que podem formar contatos favoráveis (os H no modelo HP), usados para normalizar o depósito */
+    attractive_count: f64,
+    /* Melhor fitness já depositado e iterações sem melhorá-lo, usados pelo MMAS para reiniciar */
+    best_fitness: f64,
+    stagnation: u16
+}
+
+impl Pheromones {
+    pub fn new(protein: &Protein, config: ACOConfig) -> Self {
+        let n = protein.len() - 2;
+
+        let attractive_count = protein.iter()
+            .filter(|&&aa| protein.model().is_attractive(aa))
+            .count()
+            .max(1) as f64;
+
+        let lattice = config.lattice.get();
+        let pheromones = vec![vec![INITIAL_PHEROMONE; lattice.direction_count()]; n];
+
+        let (backward, reverse) = match config.construction {
+            Construction::Forward => (Vec::new(), Vec::new()),
+            Construction::Bidirectional => (pheromones.clone(), lattice.reverse_directions())
+        };
+
+        Self {
+            pheromones,
+            backward,
+            reverse,
+            config,
+            attractive_count,
+            best_fitness: f64::NEG_INFINITY,
+            stagnation: 0
+        }
+    }
+
+    pub fn get_weight(&self, i: usize, d: Direction, h: f64) -> f64 {
+        self.pheromones[i][d.as_index()].powf(self.config.alpha) * h.powf(self.config.beta)
+    }
+
+    /// Como `get_weight`, para a formiga que estende a cadeia para trás e escolhe a direção `d`
+    /// no aminoácido `i + 1`.
+    pub fn get_backward_weight(&self, i: usize, d: Direction, h: f64) -> f64 {
+        let (i, d) = self.backward_index(i, d);
+        self.backward[i][d].powf(self.config.alpha) * h.powf(self.config.beta)
+    }
+
+    fn backward_index(&self, i: usize, d: Direction) -> (usize, usize) {
+        (self.backward.len() - 1 - i, self.reverse[d.as_index()].as_index())
+    }
+
+    /* Chama `f` com cada feromônio do caminho da conformação, nas duas matrizes */
+    fn for_each_on_path(&mut self, conf: &Conformation, mut f: impl FnMut(&mut f64)) {
+        for (i, line) in self.pheromones.iter_mut().enumerate() {
+            f(&mut line[conf.get(i).unwrap().as_index()]);
+        }
+
+        for i in 0..self.backward.len() {
+            let (j, d) = self.backward_index(i, conf.get(i).unwrap());
+            f(&mut self.backward[j][d]);
+        }
+    }
+
+    /* Todos os feromônios, das duas matrizes */
+    fn values_mut(&mut self) -> impl Iterator<Item = &mut f64> {
+        self.pheromones.iter_mut().chain(self.backward.iter_mut()).flatten()
+    }
+
+    /* Média da entropia de cada posição, normalizada pelo máximo (todas as direções iguais) */
+    pub fn entropy(&self) -> f64 {
+        if self.pheromones.is_empty() {
+            return 0.0;
+        }
+
+        let total: f64 = self.pheromones.iter()
+            .chain(&self.backward)
+            .map(|line| {
+                let sum: f64 = line.iter().sum();
+
+                if sum <= 0.0 {
+                    return 1.0;
+                }
+
+                let h: f64 = line.iter()
+                    .map(|&val| val / sum)
+                    .filter(|&p| p > 0.0)
+                    .map(|p| -p * p.ln())
+                    .sum();
+
+                h / (line.len() as f64).ln()
+            })
+            .sum();
+
+        total / (self.pheromones.len() + self.backward.len()) as f64
+    }
+
+    fn evaporate(&mut self) {
+        let evaporation = self.config.evaporation;
+
+        for value in self.values_mut() {
+            *value *= 1.0 - evaporation;
+        }
+    }
+    
+    #[cfg(feature = "visual")]
+    pub fn draw(&self) {
+        use macroquad::{shapes::draw_rectangle, window::{screen_height, screen_width}};
+
+        let width = screen_width();
+        let height = screen_height();
+        
+        let cell_w = width / self.pheromones.len() as f32;
+        let cell_h = 20.0; // altura da barra de feromônio
+        let rows = self.pheromones.first().map_or(0, Vec::len) as f32; // uma faixa por direção
+        let base_y = height - cell_h * rows - 10.0; // margem inferior
+
+        // Normaliza todos os valores para [0, 1]
+        let max_val = self.pheromones
+            .iter()
+            .flat_map(|v| v.iter())
+            .cloned()
+            .fold(f64::NAN, f64::max) // pega o máximo
+            .max(1e-6); // evita divisão por zero
+
+        for (i, line) in self.pheromones.iter().enumerate() {
+            for (d, &val) in line.iter().enumerate() {
+                let norm = (val / max_val) as f32;
+                
+                // Interpolação entre azul (baixo) e vermelho (alto)
+                let color = macroquad::color::Color {
+                    r: norm,
+                    g: 0.0,
+                    b: 1.0 - norm,
+                    a: 1.0
+                };
+
+                // Cada direção ocupa uma "faixa" dentro da coluna
+                let x = i as f32 * cell_w;
+                let y = base_y + d as f32 * (cell_h + 2.0);
+                
+                draw_rectangle(x, y, cell_w - 2.0, cell_h, color);
+            }
+        }
+    }
+}
\ No newline at end of file
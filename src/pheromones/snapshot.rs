@@ -0,0 +1,159 @@
+use std::{error::Error, fmt, fs, io, path::{Path, PathBuf}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{aco::{config::ACOConfig, WarmStart}, conformation::{lattice::LatticeKind, Conformation, Construction}, pheromones::{Pheromones, INITIAL_PHEROMONE}, protein::{protein_to_string, Protein}};
+
+/* Incrementar sempre que um campo de `PheromoneSnapshot` mudar */
+pub const PHEROMONE_FORMAT_VERSION: u32 = 1;
+
+/// Matriz de feromônios gravada em disco, com a proteína e a configuração de onde veio e a
+/// melhor conformação da execução, para continuar a busca depois ou em outra sequência.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PheromoneSnapshot {
+    pub format_version: u32,
+    pub sequence: String,
+    /* Nome do modelo de energia (`EnergyModel::name`) */
+    pub energy_model: String,
+    pub config: ACOConfig,
+    pub forward: Vec<Vec<f64>>,
+    /* Vazia se a execução não usou a construção bidirecional */
+    pub backward: Vec<Vec<f64>>,
+    /* Estado do reinício do MMAS; `None` se nada foi depositado ainda */
+    pub best_fitness: Option<f64>,
+    pub stagnation: u16,
+    /* Direções relativas, como em `Conformation::to_string` */
+    pub best_conformation: Option<String>
+}
+
+impl PheromoneSnapshot {
+    pub fn save(&self, path: &Path) -> Result<(), SnapshotError> {
+        let json = serde_json::to_string(self).map_err(|e| SnapshotError::Json(path.to_path_buf(), e))?;
+        fs::write(path, json).map_err(|e| SnapshotError::Io(path.to_path_buf(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SnapshotError> {
+        let json = fs::read_to_string(path).map_err(|e| SnapshotError::Io(path.to_path_buf(), e))?;
+        let snapshot: Self = serde_json::from_str(&json).map_err(|e| SnapshotError::Json(path.to_path_buf(), e))?;
+
+        if snapshot.format_version != PHEROMONE_FORMAT_VERSION {
+            return Err(SnapshotError::Version { found: snapshot.format_version, expected: PHEROMONE_FORMAT_VERSION });
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Ponto de partida para executar `config` em `protein` a partir deste snapshot. A proteína
+    /// pode ser outra; a rede precisa ser a mesma. A melhor conformação só é aproveitada se a
+    /// sequência for a mesma.
+    pub fn warm_start(&self, protein: &Protein, config: ACOConfig) -> Result<WarmStart, SnapshotError> {
+        let pheromones = Pheromones::from_snapshot(self, protein, config)?;
+
+        let best = match &self.best_conformation {
+            Some(s) if self.sequence == protein_to_string(protein.residues()) => {
+                let conformation = Conformation::from_relative_str(protein, config, s)
+                    .ok_or_else(|| SnapshotError::InvalidConformation(s.clone()))?;
+                Some(conformation)
+            }
+            _ => None
+        };
+
+        Ok(WarmStart { pheromones, best })
+    }
+}
+
+impl Pheromones {
+    /// Copia a matriz e o estado do MMAS para gravar em disco, junto com a melhor conformação
+    /// (se ela estiver completa).
+    pub fn snapshot(&self, protein: &Protein, best: Option<&Conformation>) -> PheromoneSnapshot {
+        PheromoneSnapshot {
+            format_version: PHEROMONE_FORMAT_VERSION,
+            sequence: protein_to_string(protein.residues()),
+            energy_model: protein.model().name().to_string(),
+            config: self.config,
+            forward: self.pheromones.clone(),
+            backward: self.backward.clone(),
+            best_fitness: self.best_fitness.is_finite().then_some(self.best_fitness),
+            stagnation: self.stagnation,
+            best_conformation: best.filter(|best| best.is_fully_grown()).map(Conformation::to_string)
+        }
+    }
+
+    /// Recria os feromônios de um snapshot para executar `config` em `protein`. Se a proteína
+    /// tiver outro tamanho, as linhas são copiadas a partir do começo de cada leitura e as que
+    /// faltarem recebem o valor inicial. A matriz inversa é descartada ou criada conforme
+    /// `config.construction`.
+    pub fn from_snapshot(snapshot: &PheromoneSnapshot, protein: &Protein, config: ACOConfig) -> Result<Self, SnapshotError> {
+        if snapshot.config.lattice != config.lattice {
+            return Err(SnapshotError::Lattice { found: snapshot.config.lattice, expected: config.lattice });
+        }
+
+        let direction_count = config.lattice.get().direction_count();
+        if let Some(row) = snapshot.forward.iter().chain(&snapshot.backward).find(|row| row.len() != direction_count) {
+            return Err(SnapshotError::DirectionCount { found: row.len(), expected: direction_count });
+        }
+
+        let mut pheromones = Pheromones::new(protein, config);
+
+        copy_rows(&mut pheromones.pheromones, &snapshot.forward);
+        if config.construction == Construction::Bidirectional {
+            /* Sem a matriz inversa salva, ela começa como a direta, como em `Pheromones::new` */
+            let backward = if snapshot.backward.is_empty() { &snapshot.forward } else { &snapshot.backward };
+            copy_rows(&mut pheromones.backward, backward);
+        }
+
+        pheromones.best_fitness = snapshot.best_fitness.unwrap_or(f64::NEG_INFINITY);
+        pheromones.stagnation = snapshot.stagnation;
+
+        Ok(pheromones)
+    }
+}
+
+fn copy_rows(rows: &mut [Vec<f64>], saved: &[Vec<f64>]) {
+    for (row, saved) in rows.iter_mut().zip(saved) {
+        row.copy_from_slice(saved);
+    }
+
+    for row in rows.iter_mut().skip(saved.len()) {
+        row.fill(INITIAL_PHEROMONE);
+    }
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(PathBuf, io::Error),
+    Json(PathBuf, serde_json::Error),
+    Version { found: u32, expected: u32 },
+    Lattice { found: LatticeKind, expected: LatticeKind },
+    DirectionCount { found: usize, expected: usize },
+    InvalidConformation(String)
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(path, e) => write!(f, "erro ao acessar '{}': {}", path.display(), e),
+            SnapshotError::Json(path, e) => write!(f, "{}: JSON inválido: {}", path.display(), e),
+            SnapshotError::Version { found, expected } => {
+                write!(f, "versão {} do arquivo de feromônios não é suportada (esperada: {})", found, expected)
+            }
+            SnapshotError::Lattice { found, expected } => {
+                write!(f, "feromônios salvos na rede {:?}, mas a execução usa a rede {:?}", found, expected)
+            }
+            SnapshotError::DirectionCount { found, expected } => {
+                write!(f, "linha de feromônios com {} direções, a rede tem {}", found, expected)
+            }
+            SnapshotError::InvalidConformation(s) => write!(f, "conformação salva inválida: '{}'", s)
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Io(_, e) => Some(e),
+            SnapshotError::Json(_, e) => Some(e),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{conformation::Conformation, pheromones::{Pheromones, INITIAL_PHEROMONE}};
+
+/// Regra de atualização dos feromônios ao fim de cada iteração.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PheromoneUpdate {
+    /* Ant System: todas as formigas depositam */
+    #[default]
+    AntSystem,
+    /* Ant System com um depósito extra da melhor solução encontrada */
+    Elitist,
+    /* As melhores formigas da iteração depositam com peso pela posição, junto com a melhor solução */
+    RankBased,
+    /* MAX–MIN Ant System: só a melhor solução deposita e os feromônios ficam entre τmin e τmax */
+    MaxMin,
+    /* Ant Colony System: atualização local no caminho de cada formiga ao terminar, antes da próxima
+    (então as formigas executam uma de cada vez), e global só na melhor solução */
+    AntColonySystem
+}
+
+/// Parâmetros das regras de atualização; cada regra só usa os seus.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateParams {
+    /* Elitista: peso do depósito da melhor solução */
+    pub elitist_weight: f64,
+    /* Rank-based: quantidade de depósitos, contando o da melhor solução */
+    pub rank_count: u16,
+    /* MMAS: probabilidade de construir a melhor solução com os feromônios convergidos, que define τmin */
+    pub p_best: f64,
+    /* MMAS: iterações sem melhora até reiniciar os feromônios em τmax */
+    pub reinit_stagnation: u16,
+    /* ACS: evaporação da atualização local, em direção ao valor inicial */
+    pub local_evaporation: f64,
+    /* ACS: probabilidade de escolher direto a direção de maior peso */
+    pub q0: f64
+}
+
+impl Default for UpdateParams {
+    fn default() -> Self {
+        Self {
+            elitist_weight: 5.0,
+            rank_count: 6,
+            p_best: 0.05,
+            reinit_stagnation: 10,
+            local_evaporation: 0.1,
+            q0: 0.9
+        }
+    }
+}
+
+impl Pheromones {
+    /// Atualiza os feromônios com as conformações da iteração e a melhor encontrada até agora.
+    /// `best` só deposita se estiver completa: sem formigas, nenhuma conformação foi construída.
+    pub fn update(&mut self, conformations: &[Conformation], best: &Conformation) {
+        let best = best.is_fully_grown().then_some(best);
+
+        match (self.config.pheromone_update, best) {
+            (PheromoneUpdate::AntSystem, _) => self.update_ant_system(conformations),
+            (PheromoneUpdate::Elitist, _) => {
+                self.update_ant_system(conformations);
+                if let Some(best) = best {
+                    self.deposit(best, self.config.update_params.elitist_weight);
+                }
+            }
+            (PheromoneUpdate::RankBased, _) => self.update_rank_based(conformations, best),
+            (PheromoneUpdate::MaxMin, Some(best)) => self.update_max_min(best),
+            (PheromoneUpdate::AntColonySystem, Some(best)) => self.update_ant_colony_system(best),
+            /* Só a melhor solução deposita nessas regras */
+            (PheromoneUpdate::MaxMin | PheromoneUpdate::AntColonySystem, None) => {}
+        }
+    }
+
+    /// Probabilidade de uma formiga seguir direto a direção de maior peso, só no ACS.
+    pub fn greedy_probability(&self) -> Option<f64> {
+        (self.config.pheromone_update == PheromoneUpdate::AntColonySystem).then_some(self.config.update_params.q0)
+    }
+
+    /// Atualização local do ACS no caminho de uma formiga que acabou de terminar, para que as
+    /// próximas formigas da iteração explorem outros caminhos. Nas outras regras não faz nada.
+    pub fn local_update(&mut self, conf: &Conformation) {
+        if !self.has_local_update() {
+            return;
+        }
+
+        let xi = self.config.update_params.local_evaporation;
+        self.for_each_on_path(conf, |value| *value = (1.0 - xi) * *value + xi * INITIAL_PHEROMONE);
+    }
+
+    /// Se cada formiga muda os feromônios ao terminar (veja `local_update`), e por isso as formigas
+    /// de uma iteração não podem executar em paralelo.
+    pub fn has_local_update(&self) -> bool {
+        self.config.pheromone_update == PheromoneUpdate::AntColonySystem
+    }
+
+    fn update_ant_system(&mut self, conformations: &[Conformation]) {
+        self.evaporate();
+
+        for conf in conformations {
+            self.deposit(conf, 1.0);
+        }
+    }
+
+    fn update_rank_based(&mut self, conformations: &[Conformation], best: Option<&Conformation>) {
+        let w = self.config.update_params.rank_count.max(1) as usize;
+
+        let mut ranked: Vec<&Conformation> = conformations.iter().collect();
+        ranked.sort_by(|a, b| b.eval().total_cmp(&a.eval())); /* Estável: empates ficam na ordem das formigas */
+
+        self.evaporate();
+
+        for (r, conf) in ranked.into_iter().take(w - 1).enumerate() {
+            self.deposit(conf, (w - 1 - r) as f64);
+        }
+        if let Some(best) = best {
+            self.deposit(best, w as f64);
+        }
+    }
+
+    fn update_max_min(&mut self, best: &Conformation) {
+        let params = self.config.update_params;
+
+        /* As trilhas começam em τmax (Stützle e Hoos), mas τmax depende da melhor solução, então
+        elas vão para τmax na primeira atualização. Na primeira iteração, qualquer valor uniforme
+        dá as mesmas probabilidades */
+        if !self.best_fitness.is_finite()
+            && let Some((_, max)) = self.trail_limits(best)
+        {
+            for value in self.values_mut() {
+                *value = max;
+            }
+        }
+
+        if best.eval() > self.best_fitness {
+            self.best_fitness = best.eval();
+            self.stagnation = 0;
+        } else {
+            self.stagnation += 1;
+        }
+
+        self.evaporate();
+        self.deposit(best, 1.0);
+
+        /* Sem limites, as trilhas ficam como estão até a melhor solução depositar algo */
+        let Some((min, max)) = self.trail_limits(best) else {
+            return;
+        };
+
+        if self.stagnation >= params.reinit_stagnation {
+            /* Reinicia a busca, com todas as direções no máximo */
+            for value in self.values_mut() {
+                *value = max;
+            }
+            self.stagnation = 0;
+        } else {
+            for value in self.values_mut() {
+                *value = value.clamp(min, max);
+            }
+        }
+    }
+
+    /* τmax = Δ(melhor) / ρ, e τmin tal que a melhor solução é construída com probabilidade `p_best`
+    quando os feromônios convergem (Stützle e Hoos). Não há limites enquanto a melhor solução tem
+    fitness 0 (τmax seria 0 e zeraria todas as trilhas) nem sem evaporação (τmax infinito) */
+    fn trail_limits(&self, best: &Conformation) -> Option<(f64, f64)> {
+        let max = self.deposit_amount(best) / self.config.evaporation;
+
+        if !max.is_finite() || max <= 0.0 {
+            return None;
+        }
+
+        let n = self.pheromones.len().max(1) as f64;
+        let avg = self.pheromones.first().map_or(2, Vec::len) as f64 / 2.0;
+        let p_dec = self.config.update_params.p_best.powf(1.0 / n);
+        let min = max * (1.0 - p_dec) / ((avg - 1.0) * p_dec);
+
+        Some((min.clamp(0.0, max), max))
+    }
+
+    /* A atualização local já foi feita formiga a formiga, em `local_update` */
+    fn update_ant_colony_system(&mut self, best: &Conformation) {
+        let rho = self.config.evaporation;
+
+        let amount = self.deposit_amount(best);
+        self.for_each_on_path(best, |value| *value = (1.0 - rho) * *value + rho * amount);
+    }
+
+    fn deposit(&mut self, conf: &Conformation, weight: f64) {
+        let amount = weight * self.deposit_amount(conf);
+
+        self.for_each_on_path(conf, |value| *value += amount);
+    }
+
+    /* Fitness normalizado; conformações com energia positiva não depositam */
+    fn deposit_amount(&self, conf: &Conformation) -> f64 {
+        conf.eval().max(0.0) / self.attractive_count
+    }
+}
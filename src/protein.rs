@@ -1,7 +0,0 @@
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum AminoAcid {
-    Polar,
-    Hydrophobic
-}
-
-pub type Protein = Vec<AminoAcid>;
@@ -0,0 +1,203 @@
+use std::{error::Error, fmt, fs, io, path::{Path, PathBuf}, str::FromStr, sync::Arc};
+
+use crate::protein::{sequence::{parse_tokens, tokenize, ParseError, ParseErrorKind, Token}, Energy, EnergyModel, Protein};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    pub name: Option<String>,
+    pub protein: Protein,
+    /* Energia da melhor conformação conhecida */
+    pub optimum: Option<Energy>
+}
+
+#[derive(Debug)]
+pub enum BenchmarkError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, ParseError),
+    NotFound { index: usize, count: usize }
+}
+
+impl fmt::Display for BenchmarkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchmarkError::Io(path, e) => write!(f, "erro ao ler '{}': {}", path.display(), e),
+            BenchmarkError::Parse(path, e) => write!(f, "{}: {}", path.display(), e),
+            BenchmarkError::NotFound { index, count } => {
+                write!(f, "benchmark {} não existe, o arquivo tem {} benchmarks", index, count)
+            }
+        }
+    }
+}
+
+impl Error for BenchmarkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BenchmarkError::Io(_, e) => Some(e),
+            BenchmarkError::Parse(_, e) => Some(e),
+            BenchmarkError::NotFound { .. } => None
+        }
+    }
+}
+
+pub fn load_benchmarks(path: &Path) -> Result<Vec<Benchmark>, BenchmarkError> {
+    load_benchmarks_with(path, &EnergyModel::hp())
+}
+
+pub fn load_benchmarks_with(path: &Path, model: &Arc<EnergyModel>) -> Result<Vec<Benchmark>, BenchmarkError> {
+    let content = fs::read_to_string(path).map_err(|e| BenchmarkError::Io(path.to_path_buf(), e))?;
+
+    parse_benchmarks_with(&content, model).map_err(|e| BenchmarkError::Parse(path.to_path_buf(), e))
+}
+
+pub fn load_benchmark(path: &Path, index: usize) -> Result<Benchmark, BenchmarkError> {
+    load_benchmark_with(path, index, &EnergyModel::hp())
+}
+
+pub fn load_benchmark_with(path: &Path, index: usize, model: &Arc<EnergyModel>) -> Result<Benchmark, BenchmarkError> {
+    let mut benchmarks = load_benchmarks_with(path, model)?;
+    let count = benchmarks.len();
+
+    if index < count {
+        Ok(benchmarks.swap_remove(index))
+    } else {
+        Err(BenchmarkError::NotFound { index, count })
+    }
+}
+
+/// Lê um conjunto de benchmarks. Cada entrada pode ser:
+///
+/// - uma linha `contatos:sequência`, o formato antigo de `benchmarks.txt`;
+/// - um registro FASTA, com cabeçalho `>nome E=-9` e a sequência nas linhas seguintes;
+/// - uma sequência solta, sem nome nem ótimo conhecido.
+///
+/// `#` inicia um comentário até o fim da linha, e linhas iniciadas por `;` são ignoradas.
+/// Sequências aceitam a notação de repetição de `parse_protein_sequence`.
+pub fn parse_benchmarks(s: &str) -> Result<Vec<Benchmark>, ParseError> {
+    parse_benchmarks_with(s, &EnergyModel::hp())
+}
+
+/// Como `parse_benchmarks`, com as sequências no alfabeto de `model`.
+pub fn parse_benchmarks_with(s: &str, model: &Arc<EnergyModel>) -> Result<Vec<Benchmark>, ParseError> {
+    let mut benchmarks = Vec::new();
+    let mut record: Option<Record> = None;
+
+    for (i, raw_line) in s.lines().enumerate() {
+        let line_number = i + 1;
+
+        let line = match raw_line.find('#') {
+            Some(comment) => &raw_line[..comment],
+            None => raw_line
+        };
+
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with(';') {
+            continue;
+        }
+
+        if trimmed.trim_end().is_empty() {
+            if let Some(record) = record.take() {
+                benchmarks.push(record.finish(model)?);
+            }
+            continue;
+        }
+
+        /* Um cabeçalho ou uma linha no formato antigo encerra a entrada anterior, mesmo sem linha em branco */
+        let legacy = line.split_once(':');
+        if (trimmed.starts_with('>') || legacy.is_some()) && let Some(record) = record.take() {
+            benchmarks.push(record.finish(model)?);
+        }
+
+        if let Some(header) = trimmed.strip_prefix('>') {
+            let column = char_column(line, header);
+            record = Some(Record::from_header(header, line_number, column)?);
+        } else if let Some((optimum, sequence)) = legacy {
+            /* No formato antigo o ótimo é a quantidade de contatos H-H */
+            let contacts: i32 = parse_number(optimum, line_number, char_column(line, optimum))?;
+            let tokens: Vec<Token> = tokenize(sequence, line_number, char_column(line, sequence)).collect();
+
+            benchmarks.push(Benchmark {
+                name: None,
+                protein: parse_tokens(&tokens, (line_number, char_column(line, sequence)), model)?,
+                optimum: Some(Energy::from_contacts(contacts))
+            });
+        } else if let Some(record) = record.as_mut() {
+            record.tokens.extend(tokenize(line, line_number, 1));
+        } else {
+            record = Some(Record {
+                name: None,
+                optimum: None,
+                line: line_number,
+                tokens: tokenize(line, line_number, 1).collect()
+            });
+        }
+    }
+
+    if let Some(record) = record {
+        benchmarks.push(record.finish(model)?);
+    }
+
+    Ok(benchmarks)
+}
+
+/* Entrada em construção, que pode se estender por várias linhas */
+struct Record {
+    name: Option<String>,
+    optimum: Option<Energy>,
+    line: usize,
+    tokens: Vec<Token>
+}
+
+impl Record {
+    fn from_header(header: &str, line: usize, column: usize) -> Result<Self, ParseError> {
+        let mut name = None;
+        let mut optimum = None;
+
+        for (i, word) in header.split_whitespace().enumerate() {
+            let word_column = column + header[..offset_of(header, word)].chars().count();
+
+            match word.split_once('=') {
+                Some((key, value)) if key.eq_ignore_ascii_case("e") || key.eq_ignore_ascii_case("energy") => {
+                    let value_column = word_column + key.chars().count() + 1;
+                    let energy: f64 = parse_number(value, line, value_column)?;
+
+                    if energy > 0.0 || !energy.is_finite() {
+                        return Err(ParseError { line, column: value_column, kind: ParseErrorKind::InvalidEnergy(value.to_string()) });
+                    }
+
+                    optimum = Some(Energy::new(energy));
+                }
+                Some(_) => {} /* Outros atributos são ignorados */
+                None if i == 0 => name = Some(word.to_string()),
+                None => {} /* Descrição livre */
+            }
+        }
+
+        Ok(Self { name, optimum, line, tokens: Vec::new() })
+    }
+
+    fn finish(self, model: &Arc<EnergyModel>) -> Result<Benchmark, ParseError> {
+        Ok(Benchmark {
+            name: self.name,
+            protein: parse_tokens(&self.tokens, (self.line + 1, 1), model)?,
+            optimum: self.optimum
+        })
+    }
+}
+
+fn parse_number<T: FromStr>(s: &str, line: usize, column: usize) -> Result<T, ParseError> {
+    let trimmed = s.trim();
+    let column = column + s[..offset_of(s, trimmed)].chars().count();
+
+    trimmed.parse().map_err(|_| ParseError { line, column, kind: ParseErrorKind::InvalidEnergy(trimmed.to_string()) })
+}
+
+/* Posição (em bytes) de `part` dentro de `s`, onde `part` é um pedaço de `s` */
+fn offset_of(s: &str, part: &str) -> usize {
+    part.as_ptr() as usize - s.as_ptr() as usize
+}
+
+/* Coluna (começando em 1) onde `part` começa dentro da linha */
+fn char_column(line: &str, part: &str) -> usize {
+    line[..offset_of(line, part)].chars().count() + 1
+}
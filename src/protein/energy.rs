@@ -0,0 +1,349 @@
+use std::{error::Error, fmt, fs, io, path::{Path, PathBuf}, sync::{Arc, LazyLock}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::protein::AminoAcid;
+
+/* Maior escala usada para guardar energias como inteiros (4 casas decimais) */
+const MAX_SCALE: i32 = 10_000;
+
+const ABSENT: u8 = u8::MAX;
+
+static HP: LazyLock<Arc<EnergyModel>> = LazyLock::new(|| {
+    Arc::new(EnergyModel::new("hp", "HP", &[
+        vec![-1.0, 0.0],
+        vec![0.0, 0.0]
+    ]).unwrap())
+});
+
+/* Backofen e Will: H hidrofóbico, P e N com cargas opostas e X neutro */
+static HPNX: LazyLock<Arc<EnergyModel>> = LazyLock::new(|| {
+    Arc::new(EnergyModel::new("hpnx", "HPNX", &[
+        vec![-4.0, 0.0, 0.0, 0.0],
+        vec![0.0, 1.0, -1.0, 0.0],
+        vec![0.0, -1.0, 1.0, 0.0],
+        vec![0.0, 0.0, 0.0, 0.0]
+    ]).unwrap())
+});
+
+/// Energia livre de uma conformação, na convenção da literatura e dos benchmarks: quanto mais
+/// negativa, melhor. O ACO maximiza o fitness de `Conformation::eval`, que é o seu negativo
+/// (no modelo HP, a quantidade de contatos H-H).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Energy(f64);
+
+impl Energy {
+    pub const fn new(value: f64) -> Self {
+        Self(value)
+    }
+
+    /* `0.0 - x` em vez de `-x` para não gerar `-0` */
+    pub fn from_fitness(fitness: f64) -> Self {
+        Self(0.0 - fitness)
+    }
+
+    /// Energia no modelo HP de uma conformação com `contacts` contatos H-H.
+    pub fn from_contacts(contacts: i32) -> Self {
+        Self::from_fitness(contacts as f64)
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    pub fn fitness(&self) -> f64 {
+        0.0 - self.0
+    }
+
+    /// Falso para a energia de "nenhuma conformação ainda", como a melhor de uma execução sem
+    /// nenhuma iteração.
+    pub fn is_finite(&self) -> bool {
+        self.0.is_finite()
+    }
+
+    pub fn is_better_than(&self, other: Energy) -> bool {
+        self.0 < other.0
+    }
+
+    /// Quanto falta para o ótimo, relativo a ele: `(E - E*) / |E*|`. É 0 ao atingir o ótimo e 1
+    /// com energia 0. Retorna `None` se o ótimo for 0.
+    pub fn relative_error_to_optimum(&self, optimum: Energy) -> Option<f64> {
+        (optimum.0 != 0.0).then(|| (self.0 - optimum.0) / optimum.0.abs())
+    }
+}
+
+impl fmt::Display for Energy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Modelo de energia de contato: cada par de aminoácidos vizinhos na rede, e não consecutivos
+/// na cadeia, contribui com a energia da matriz de interação. Energias negativas são favoráveis.
+///
+/// As energias são guardadas como inteiros em unidades de `1 / scale`, assim as variações
+/// incrementais são exatas e movimentos neutros continuam sendo reconhecidos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnergyModel {
+    name: String,
+    alphabet: Vec<AminoAcid>,
+    /* Posição de cada letra (ASCII) no alfabeto */
+    index: [u8; 128],
+    matrix: Vec<i32>,
+    /* Se o aminoácido tem alguma interação favorável ou alguma interação, na ordem do alfabeto */
+    attractive: Vec<bool>,
+    interacting: Vec<bool>,
+    scale: i32
+}
+
+impl EnergyModel {
+    /// Monta um modelo a partir de uma matriz simétrica, com linhas e colunas na ordem de `alphabet`.
+    pub fn new(name: &str, alphabet: &str, matrix: &[Vec<f64>]) -> Result<Self, EnergyError> {
+        let alphabet: Vec<AminoAcid> = alphabet.chars()
+            .map(|c| AminoAcid::from_char(c).ok_or_else(|| EnergyError::InvalidResidue { line: None, residue: c.to_string() }))
+            .collect::<Result<_, _>>()?;
+
+        let n = alphabet.len();
+        if n == 0 {
+            return Err(EnergyError::EmptyMatrix);
+        }
+
+        let mut index = [ABSENT; 128];
+        for (i, aa) in alphabet.iter().enumerate() {
+            if index[aa.char() as usize] != ABSENT {
+                return Err(EnergyError::DuplicateResidue(*aa));
+            }
+            index[aa.char() as usize] = i as u8;
+        }
+
+        /* Antes da simetria, que lê `matrix[j][i]` para toda linha `i` */
+        if matrix.len() < n {
+            return Err(EnergyError::MissingRow(alphabet[matrix.len()]));
+        }
+        if matrix.len() > n {
+            return Err(EnergyError::ExtraRows { expected: n, found: matrix.len() });
+        }
+
+        for (i, row) in matrix.iter().enumerate() {
+            if row.len() != n {
+                return Err(EnergyError::RowLength { line: None, expected: n, found: row.len() });
+            }
+
+            if let Some(j) = (0..i).find(|&j| row[j] != matrix[j][i]) {
+                return Err(EnergyError::Asymmetric(alphabet[i], alphabet[j]));
+            }
+        }
+
+        let values: Vec<f64> = matrix.iter().flatten().copied().collect();
+
+        /* Menor potência de 10 que deixa todas as energias inteiras */
+        let mut scale = 1;
+        while scale < MAX_SCALE && values.iter().any(|&e| (e * scale as f64).fract().abs() > 1e-9) {
+            scale *= 10;
+        }
+
+        let attractive = matrix.iter().map(|row| row.iter().any(|&e| e < 0.0)).collect();
+        let interacting = matrix.iter().map(|row| row.iter().any(|&e| e != 0.0)).collect();
+
+        Ok(Self {
+            name: name.to_string(),
+            alphabet,
+            index,
+            matrix: values.iter().map(|&e| (e * scale as f64).round() as i32).collect(),
+            attractive,
+            interacting,
+            scale
+        })
+    }
+
+    /// Modelo HP: só contatos H-H, com energia -1.
+    pub fn hp() -> Arc<Self> {
+        HP.clone()
+    }
+
+    /// Modelo HPNX, com a matriz 4×4 de Backofen e Will.
+    pub fn hpnx() -> Arc<Self> {
+        HPNX.clone()
+    }
+
+    /// Modelo embutido pelo nome (`hp` ou `hpnx`), como gravado em `EnergyModel::name`.
+    pub fn builtin(name: &str) -> Option<Arc<Self>> {
+        match name {
+            "hp" => Some(Self::hp()),
+            "hpnx" => Some(Self::hpnx()),
+            _ => None
+        }
+    }
+
+    /// Lê uma matriz de interação, como a de Miyazawa–Jernigan. A primeira linha lista os
+    /// aminoácidos e cada linha seguinte começa com um aminoácido e traz suas energias, com a
+    /// linha inteira ou só até a diagonal (matriz triangular inferior). `#` inicia um comentário.
+    pub fn parse_matrix(name: &str, s: &str) -> Result<Self, EnergyError> {
+        let mut lines = s.lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.split('#').next().unwrap().trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let Some((header_line, header)) = lines.next() else {
+            return Err(EnergyError::EmptyMatrix);
+        };
+
+        let alphabet: String = header.split_whitespace()
+            .map(|word| parse_residue(word, header_line).map(|aa| aa.char()))
+            .collect::<Result<_, _>>()?;
+
+        let n = alphabet.chars().count();
+        let mut rows: Vec<Option<Vec<f64>>> = vec![None; n];
+
+        for (line_number, line) in lines {
+            let mut words = line.split_whitespace();
+            let residue = parse_residue(words.next().unwrap(), line_number)?;
+
+            let Some(i) = alphabet.chars().position(|c| c == residue.char()) else {
+                return Err(EnergyError::InvalidResidue { line: Some(line_number), residue: residue.to_string() });
+            };
+
+            let values: Vec<f64> = words
+                .map(|word| word.parse().map_err(|_| EnergyError::InvalidEnergy { line: line_number, value: word.to_string() }))
+                .collect::<Result<_, _>>()?;
+
+            if values.len() != n && values.len() != i + 1 {
+                return Err(EnergyError::RowLength { line: Some(line_number), expected: n, found: values.len() });
+            }
+
+            if rows[i].replace(values).is_some() {
+                return Err(EnergyError::DuplicateResidue(residue));
+            }
+        }
+
+        let mut matrix = vec![vec![0.0; n]; n];
+        for (i, row) in rows.iter().enumerate() {
+            let Some(row) = row else {
+                return Err(EnergyError::MissingRow(AminoAcid::from_char(alphabet.chars().nth(i).unwrap()).unwrap()));
+            };
+
+            matrix[i][..row.len()].copy_from_slice(row);
+        }
+
+        /* Completa a parte de cima das linhas dadas só até a diagonal */
+        for (i, row) in rows.iter().enumerate() {
+            if row.as_ref().unwrap().len() == i + 1 {
+                let column: Vec<f64> = matrix[i + 1..].iter().map(|other| other[i]).collect();
+                matrix[i][i + 1..].copy_from_slice(&column);
+            }
+        }
+
+        EnergyModel::new(name, &alphabet, &matrix)
+    }
+
+    /// Como `parse_matrix`, lendo de um arquivo; o nome do modelo é o nome do arquivo.
+    pub fn load(path: &Path) -> Result<Self, EnergyError> {
+        let content = fs::read_to_string(path).map_err(|e| EnergyError::Io(path.to_path_buf(), e))?;
+        let name = path.file_stem().map_or("matriz".into(), |stem| stem.to_string_lossy());
+
+        EnergyModel::parse_matrix(&name, &content)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn alphabet(&self) -> &[AminoAcid] {
+        &self.alphabet
+    }
+
+    pub fn contains(&self, aa: AminoAcid) -> bool {
+        self.index[aa.char() as usize] != ABSENT
+    }
+
+    /// Quantas unidades inteiras de energia formam uma unidade real.
+    pub fn scale(&self) -> f64 {
+        self.scale as f64
+    }
+
+    /* Os aminoácidos da proteína já foram validados contra o alfabeto */
+    fn position(&self, aa: AminoAcid) -> usize {
+        self.index[aa.char() as usize] as usize
+    }
+
+    /// Energia do contato entre `a` e `b`, em unidades de `1 / scale`.
+    pub fn interaction(&self, a: AminoAcid, b: AminoAcid) -> i32 {
+        self.matrix[self.position(a) * self.alphabet.len() + self.position(b)]
+    }
+
+    pub fn energy(&self, a: AminoAcid, b: AminoAcid) -> f64 {
+        self.interaction(a, b) as f64 / self.scale()
+    }
+
+    /// Se `a` tem alguma interação favorável, como o H no modelo HP.
+    pub fn is_attractive(&self, a: AminoAcid) -> bool {
+        self.attractive[self.position(a)]
+    }
+
+    /// Se algum contato de `a` muda a energia, como o H no modelo HP.
+    pub fn interacts(&self, a: AminoAcid) -> bool {
+        self.interacting[self.position(a)]
+    }
+}
+
+fn parse_residue(word: &str, line: usize) -> Result<AminoAcid, EnergyError> {
+    let mut chars = word.chars();
+
+    match (chars.next().and_then(AminoAcid::from_char), chars.next()) {
+        (Some(aa), None) => Ok(aa),
+        _ => Err(EnergyError::InvalidResidue { line: Some(line), residue: word.to_string() })
+    }
+}
+
+#[derive(Debug)]
+pub enum EnergyError {
+    Io(PathBuf, io::Error),
+    EmptyMatrix,
+    InvalidResidue { line: Option<usize>, residue: String },
+    InvalidEnergy { line: usize, value: String },
+    RowLength { line: Option<usize>, expected: usize, found: usize },
+    DuplicateResidue(AminoAcid),
+    MissingRow(AminoAcid),
+    ExtraRows { expected: usize, found: usize },
+    Asymmetric(AminoAcid, AminoAcid),
+    /* Aminoácido de uma proteína que não existe no modelo */
+    UnknownResidue { position: usize, residue: AminoAcid, model: String }
+}
+
+impl fmt::Display for EnergyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let EnergyError::InvalidResidue { line: Some(line), .. } | EnergyError::InvalidEnergy { line, .. }
+            | EnergyError::RowLength { line: Some(line), .. } = self {
+            write!(f, "linha {}: ", line)?;
+        }
+
+        match self {
+            EnergyError::Io(path, e) => write!(f, "erro ao ler '{}': {}", path.display(), e),
+            EnergyError::EmptyMatrix => write!(f, "matriz de interação vazia"),
+            EnergyError::InvalidResidue { residue, .. } => write!(f, "aminoácido inválido: '{}'", residue),
+            EnergyError::InvalidEnergy { value, .. } => write!(f, "energia inválida: '{}'", value),
+            EnergyError::RowLength { expected, found, .. } => {
+                write!(f, "linha da matriz com {} energias, eram esperadas {}", found, expected)
+            }
+            EnergyError::DuplicateResidue(aa) => write!(f, "aminoácido '{}' repetido na matriz", aa),
+            EnergyError::MissingRow(aa) => write!(f, "falta a linha do aminoácido '{}' na matriz", aa),
+            EnergyError::ExtraRows { expected, found } => {
+                write!(f, "matriz com {} linhas, mas o alfabeto tem {} aminoácidos", found, expected)
+            }
+            EnergyError::Asymmetric(a, b) => write!(f, "matriz não simétrica entre '{}' e '{}'", a, b),
+            EnergyError::UnknownResidue { position, residue, model } => {
+                write!(f, "aminoácido '{}' na posição {} não existe no modelo '{}'", residue, position + 1, model)
+            }
+        }
+    }
+}
+
+impl Error for EnergyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EnergyError::Io(_, e) => Some(e),
+            _ => None
+        }
+    }
+}
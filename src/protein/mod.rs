@@ -0,0 +1,85 @@
+use std::{fmt, ops::Index, slice, sync::Arc};
+
+pub mod benchmark;
+pub mod energy;
+pub mod sequence;
+
+pub use benchmark::{load_benchmark, load_benchmark_with, load_benchmarks, load_benchmarks_with, parse_benchmarks, parse_benchmarks_with, Benchmark, BenchmarkError};
+pub use energy::{Energy, EnergyError, EnergyModel};
+pub use sequence::{parse_protein_sequence, parse_protein_sequence_with, ParseError, ParseErrorKind};
+
+/// Aminoácido, identificado pela sua letra. O significado da letra depende do modelo de energia:
+/// `H` e `P` no modelo HP, `H`, `P`, `N` e `X` no HPNX ou o código de uma letra em matrizes
+/// como a de Miyazawa–Jernigan.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AminoAcid(u8);
+
+impl AminoAcid {
+    pub const HYDROPHOBIC: AminoAcid = AminoAcid(b'H');
+    pub const POLAR: AminoAcid = AminoAcid(b'P');
+
+    /* Letras minúsculas são aceitas e guardadas como maiúsculas */
+    pub fn from_char(c: char) -> Option<Self> {
+        c.is_ascii_alphabetic().then(|| AminoAcid(c.to_ascii_uppercase() as u8))
+    }
+
+    pub fn char(&self) -> char {
+        self.0 as char
+    }
+}
+
+impl fmt::Display for AminoAcid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.char())
+    }
+}
+
+/// Sequência de aminoácidos junto com o modelo de energia que dá sentido às suas letras.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Protein {
+    residues: Vec<AminoAcid>,
+    model: Arc<EnergyModel>
+}
+
+impl Protein {
+    /// Falha se algum aminoácido não existir no alfabeto do modelo.
+    pub fn new(residues: Vec<AminoAcid>, model: Arc<EnergyModel>) -> Result<Self, EnergyError> {
+        if let Some(position) = residues.iter().position(|&aa| !model.contains(aa)) {
+            return Err(EnergyError::UnknownResidue { position, residue: residues[position], model: model.name().to_string() });
+        }
+
+        Ok(Self { residues, model })
+    }
+
+    pub fn residues(&self) -> &[AminoAcid] {
+        &self.residues
+    }
+
+    pub fn model(&self) -> &EnergyModel {
+        &self.model
+    }
+
+    pub fn len(&self) -> usize {
+        self.residues.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.residues.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, AminoAcid> {
+        self.residues.iter()
+    }
+}
+
+impl Index<usize> for Protein {
+    type Output = AminoAcid;
+
+    fn index(&self, i: usize) -> &AminoAcid {
+        &self.residues[i]
+    }
+}
+
+pub fn protein_to_string(protein: &[AminoAcid]) -> String {
+    protein.iter().map(AminoAcid::char).collect()
+}
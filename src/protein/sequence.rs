@@ -0,0 +1,164 @@
+use std::{error::Error, fmt, sync::Arc};
+
+use crate::protein::{AminoAcid, EnergyModel, Protein};
+
+/* Menor proteína com ao menos uma direção livre (os dois primeiros aminoácidos são fixos) */
+pub const MIN_PROTEIN_LEN: usize = 3;
+
+/* Limite das repetições e do tamanho da sequência expandida, para que `H4000000000` seja um erro
+   e não esgote a memória (as quantidades de formigas e iterações também são u16) */
+pub const MAX_PROTEIN_LEN: usize = u16::MAX as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidResidue(char),
+    InvalidCount(String),
+    CountTooLarge(String),
+    CountWithoutResidue,
+    UnclosedParen,
+    UnexpectedCloseParen,
+    EmptySequence,
+    TooShort(usize),
+    TooLong,
+    InvalidHeader(String),
+    InvalidEnergy(String)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "linha {}, coluna {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidResidue(c) => write!(f, "caractere inválido na sequência: '{}'", c),
+            ParseErrorKind::InvalidCount(count) => write!(f, "repetição inválida: '{}'", count),
+            ParseErrorKind::CountTooLarge(count) => write!(f, "repetição '{}' maior que o máximo de {}", count, MAX_PROTEIN_LEN),
+            ParseErrorKind::CountWithoutResidue => write!(f, "repetição sem aminoácido ou grupo antes"),
+            ParseErrorKind::UnclosedParen => write!(f, "parêntese não fechado"),
+            ParseErrorKind::UnexpectedCloseParen => write!(f, "parêntese fechado sem abertura"),
+            ParseErrorKind::EmptySequence => write!(f, "sequência vazia"),
+            ParseErrorKind::TooShort(len) => write!(f, "sequência com {} aminoácidos, o mínimo é {}", len, MIN_PROTEIN_LEN),
+            ParseErrorKind::TooLong => write!(f, "sequência com mais de {} aminoácidos", MAX_PROTEIN_LEN),
+            ParseErrorKind::InvalidHeader(header) => write!(f, "cabeçalho inválido: '{}'", header),
+            ParseErrorKind::InvalidEnergy(energy) => write!(f, "energia inválida: '{}'", energy)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/* Caractere de uma sequência com sua posição (linha e coluna começam em 1) */
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Token {
+    pub c: char,
+    pub line: usize,
+    pub column: usize
+}
+
+impl Token {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError { line: self.line, column: self.column, kind }
+    }
+}
+
+pub(crate) fn tokenize(s: &str, line: usize, first_column: usize) -> impl Iterator<Item = Token> + '_ {
+    s.chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(move |(i, c)| Token { c, line, column: first_column + i })
+}
+
+/// Lê uma sequência HP, aceitando a notação de repetição: `H2P2(HP)3` é `HHPPHPHPHP`.
+pub fn parse_protein_sequence(s: &str) -> Result<Protein, ParseError> {
+    parse_protein_sequence_with(s, &EnergyModel::hp())
+}
+
+/// Como `parse_protein_sequence`, aceitando as letras do alfabeto de `model`.
+pub fn parse_protein_sequence_with(s: &str, model: &Arc<EnergyModel>) -> Result<Protein, ParseError> {
+    let tokens: Vec<Token> = tokenize(s, 1, 1).collect();
+    parse_tokens(&tokens, (1, 1), model)
+}
+
+/* `start` é a posição reportada quando a sequência está vazia */
+pub(crate) fn parse_tokens(tokens: &[Token], start: (usize, usize), model: &Arc<EnergyModel>) -> Result<Protein, ParseError> {
+    let mut idx = 0;
+    let residues = parse_group(tokens, &mut idx, None, model)?;
+
+    let (line, column) = tokens.first().map_or(start, |t| (t.line, t.column));
+
+    match residues.len() {
+        0 => Err(ParseError { line, column, kind: ParseErrorKind::EmptySequence }),
+        len if len < MIN_PROTEIN_LEN => Err(ParseError { line, column, kind: ParseErrorKind::TooShort(len) }),
+        _ => Ok(Protein { residues, model: model.clone() })
+    }
+}
+
+/* `open` é o parêntese que abriu o grupo atual, se houver */
+fn parse_group(tokens: &[Token], idx: &mut usize, open: Option<Token>, model: &EnergyModel) -> Result<Vec<AminoAcid>, ParseError> {
+    let mut protein = Vec::new();
+
+    while let Some(&token) = tokens.get(*idx) {
+        *idx += 1;
+
+        let item = match token.c {
+            '(' => parse_group(tokens, idx, Some(token), model)?,
+            ')' => {
+                return match open {
+                    Some(_) => Ok(protein),
+                    None => Err(token.error(ParseErrorKind::UnexpectedCloseParen))
+                };
+            }
+            c if c.is_ascii_digit() => return Err(token.error(ParseErrorKind::CountWithoutResidue)),
+            c => match AminoAcid::from_char(c).filter(|&aa| model.contains(aa)) {
+                Some(aa) => vec![aa],
+                None => return Err(token.error(ParseErrorKind::InvalidResidue(c)))
+            }
+        };
+
+        let count = parse_count(tokens, idx)?;
+
+        if protein.len() + item.len() * count > MAX_PROTEIN_LEN {
+            return Err(token.error(ParseErrorKind::TooLong));
+        }
+
+        for _ in 0..count {
+            protein.extend_from_slice(&item);
+        }
+    }
+
+    match open {
+        Some(open) => Err(open.error(ParseErrorKind::UnclosedParen)),
+        None => Ok(protein)
+    }
+}
+
+fn parse_count(tokens: &[Token], idx: &mut usize) -> Result<usize, ParseError> {
+    let start = *idx;
+
+    while tokens.get(*idx).is_some_and(|t| t.c.is_ascii_digit()) {
+        *idx += 1;
+    }
+
+    if start == *idx {
+        return Ok(1);
+    }
+
+    let digits: String = tokens[start..*idx].iter().map(|t| t.c).collect();
+
+    /* Só dígitos: o único erro de `parse` é um número grande demais */
+    match digits.parse::<usize>() {
+        Ok(0) => Err(tokens[start].error(ParseErrorKind::InvalidCount(digits))),
+        Ok(count) if count <= MAX_PROTEIN_LEN => Ok(count),
+        _ => Err(tokens[start].error(ParseErrorKind::CountTooLarge(digits)))
+    }
+}
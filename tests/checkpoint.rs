@@ -0,0 +1,106 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use ic_rust::{aco::{checkpoint::CheckpointError, logger::{ACOLogger, Event}, report::IterationStats, stop::StopReason}, aco_protein_folding_2dhp, aco_protein_folding_2dhp_checkpointed, protein::{parse_protein_sequence, parse_protein_sequence_with}, resume_from, resume_from_with, run_checkpointed, ACOConfig, AcoEngine, CancellationToken, Checkpoint, EnergyModel, RunReport};
+
+fn config(pheromone_update: &str) -> ACOConfig {
+    serde_json::from_str(&format!(r#"{{
+        "ant_count": 6, "max_iter": 10, "no_impr_max": 10, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+        "neutral_mutation_rate": 0.5, "seed": 11, "pheromone_update": "{}"
+    }}"#, pheromone_update)).unwrap()
+}
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ic_rust_{}_{}.json", name, std::process::id()))
+}
+
+/* Cancela a execução ao fim da iteração `after` */
+struct CancelAfter {
+    token: CancellationToken,
+    after: u16
+}
+
+impl ACOLogger for CancelAfter {
+    fn log(&mut self, event: &Event<'_>) {
+        if let Event::Iteration { stats, .. } = event
+            && stats.iteration == self.after
+        {
+            self.token.cancel();
+        }
+    }
+}
+
+fn untimed(report: &RunReport) -> Vec<IterationStats> {
+    report.iterations.iter().map(|stats| IterationStats { elapsed_secs: 0.0, ..*stats }).collect()
+}
+
+/* Cancelar, salvar e continuar do checkpoint dá o mesmo resultado da execução sem interrupção */
+#[test]
+fn resume_matches_uninterrupted_run() {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+
+    for rule in ["ant_system", "max_min", "ant_colony_system"] {
+        let config = config(rule);
+        let path = checkpoint_path(rule);
+        let expected = aco_protein_folding_2dhp(&protein, config, ());
+
+        let token = CancellationToken::new();
+        let engine = AcoEngine::new(&protein, config).with_cancellation(token.clone());
+        let cancelled = run_checkpointed(engine, CancelAfter { token, after: 3 }, &path, 100).unwrap();
+        assert_eq!(cancelled.stop_reason, StopReason::Cancelled);
+        assert_eq!(Checkpoint::load(&path).unwrap().iteration(), 4);
+
+        let resumed = resume_from(&path, ()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(resumed.best_conformation.to_string(), expected.best_conformation.to_string(), "{}", rule);
+        assert_eq!(resumed.best, expected.best);
+        assert_eq!(resumed.best_iteration, expected.best_iteration);
+        assert_eq!(untimed(&resumed), untimed(&expected));
+        assert_eq!(resumed.evaluations, expected.evaluations);
+        assert_eq!(resumed.stop_reason, expected.stop_reason);
+        assert_eq!(resumed.pheromones, expected.pheromones);
+    }
+}
+
+/* Cancelada antes da primeira iteração, a execução não tem o que continuar */
+#[test]
+fn cancel_before_first_iteration_saves_no_checkpoint() {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+    let path = checkpoint_path("cancelled_at_start");
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let engine = AcoEngine::new(&protein, config("ant_system")).with_cancellation(token);
+    let report = run_checkpointed(engine, (), &path, 1).unwrap();
+
+    assert_eq!(report.stop_reason, StopReason::Cancelled);
+    assert!(report.iterations.is_empty());
+    assert!(!path.exists());
+}
+
+/* O checkpoint guarda só o nome do modelo: os embutidos são refeitos, uma matriz precisa ser passada de novo */
+#[test]
+fn resume_rebuilds_builtin_models_and_checks_custom_ones() {
+    let hpnx = EnergyModel::hpnx();
+    let custom = Arc::new(EnergyModel::parse_matrix("custom", "H P\nH -2\nP 0 0").unwrap());
+
+    for (name, model) in [("hpnx", hpnx), ("custom", custom.clone())] {
+        let protein = parse_protein_sequence_with("HPHPPHHPHPPH", &model).unwrap();
+        let path = checkpoint_path(&format!("model_{}", name));
+        aco_protein_folding_2dhp_checkpointed(&protein, ACOConfig { max_iter: 2, ..config("ant_system") }, (), &path, 1).unwrap();
+
+        let checkpoint = Checkpoint::load(&path).unwrap();
+        match name {
+            "hpnx" => assert_eq!(checkpoint.protein().unwrap(), protein),
+            _ => {
+                assert!(matches!(checkpoint.protein(), Err(CheckpointError::CustomModel(name)) if name == "custom"));
+                assert!(matches!(resume_from(&path, ()), Err(CheckpointError::CustomModel(_))));
+                assert!(matches!(resume_from_with(&path, &EnergyModel::hp(), ()), Err(CheckpointError::Protein { .. })));
+                assert_eq!(checkpoint.protein_with(&custom).unwrap(), protein);
+            }
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
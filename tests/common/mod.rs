@@ -0,0 +1,59 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use ic_rust::{ACOConfig, Conformation, LatticeKind, Pheromones, Protein};
+
+pub const LATTICES: [LatticeKind; 4] = [LatticeKind::Square, LatticeKind::Triangular, LatticeKind::Cubic, LatticeKind::Fcc];
+
+pub fn config(lattice: LatticeKind) -> ACOConfig {
+    let config: ACOConfig = serde_json::from_str(r#"{
+        "ant_count": 1, "max_iter": 1, "no_impr_max": 1, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+        "neutral_mutation_rate": 0.5, "seed": 0
+    }"#).unwrap();
+
+    ACOConfig { lattice, ..config }
+}
+
+/* Conformação completa construída com os feromônios iniciais, como a de uma formiga */
+pub fn random_conformation<R: Rng>(protein: &Protein, config: ACOConfig, rng: &mut R) -> Conformation {
+    let pheromones = Pheromones::new(protein, config);
+    let mut conformation = Conformation::new(protein, config);
+
+    while !conformation.is_fully_grown() {
+        if !conformation.grow(&pheromones, rng) {
+            conformation.rewind();
+        }
+    }
+
+    conformation
+}
+
+/* Energia calculada do zero pelas coordenadas, comparando todos os pares de aminoácidos */
+pub fn full_eval(protein: &Protein, conformation: &Conformation) -> f64 {
+    let lattice = conformation.lattice();
+    let positions = conformation.coordinates();
+    let model = protein.model();
+
+    let mut score = 0;
+    for i in 0..positions.len() {
+        for j in i + 2..positions.len() {
+            if lattice.are_adjacent(positions[i], positions[j]) {
+                score -= model.interaction(protein.residues()[i], protein.residues()[j]);
+            }
+        }
+    }
+
+    score as f64 / model.scale()
+}
+
+/* Como `full_eval`, reconstruindo a conformação só pelas direções relativas */
+pub fn rebuilt_eval(protein: &Protein, conformation: &Conformation) -> Option<f64> {
+    let directions: Vec<_> = conformation.directions().iter().map(|d| d.unwrap()).collect();
+    let rebuilt = Conformation::from_directions(protein, conformation.config(), &directions)?;
+
+    Some(full_eval(protein, &rebuilt))
+}
+
+pub fn rng(seed: u64) -> ChaCha8Rng {
+    ic_rust::aco::ant_rng(seed, 0, 0)
+}
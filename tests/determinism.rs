@@ -0,0 +1,40 @@
+use ic_rust::{aco::report::IterationStats, aco_protein_folding_2dhp, protein::parse_protein_sequence, ACOConfig, RunReport};
+
+fn config(pheromone_update: &str) -> ACOConfig {
+    serde_json::from_str(&format!(r#"{{
+        "ant_count": 6, "max_iter": 8, "no_impr_max": 10, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+        "neutral_mutation_rate": 0.5, "seed": 7, "pheromone_update": "{}"
+    }}"#, pheromone_update)).unwrap()
+}
+
+fn run_with_threads(threads: usize, config: ACOConfig) -> RunReport {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+
+    pool.install(|| aco_protein_folding_2dhp(&protein, config, ()))
+}
+
+/* Estatísticas sem o tempo, a única coisa que muda de uma execução para outra */
+fn untimed(report: &RunReport) -> Vec<IterationStats> {
+    report.iterations.iter().map(|stats| IterationStats { elapsed_secs: 0.0, ..*stats }).collect()
+}
+
+/* A mesma seed dá o mesmo resultado com qualquer quantidade de threads (como RAYON_NUM_THREADS) */
+#[test]
+fn same_seed_same_report_with_any_thread_count() {
+    for rule in ["ant_system", "max_min", "ant_colony_system"] {
+        let expected = run_with_threads(1, config(rule));
+
+        for threads in [2, 4, 8] {
+            let report = run_with_threads(threads, config(rule));
+
+            assert_eq!(report.best_conformation.to_string(), expected.best_conformation.to_string(), "{} com {} threads", rule, threads);
+            assert_eq!(report.best, expected.best);
+            assert_eq!(report.best_iteration, expected.best_iteration);
+            assert_eq!(untimed(&report), untimed(&expected));
+            assert_eq!(report.evaluations, expected.evaluations);
+            assert_eq!(report.stop_reason, expected.stop_reason);
+            assert_eq!(report.pheromones, expected.pheromones);
+        }
+    }
+}
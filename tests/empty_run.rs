@@ -0,0 +1,81 @@
+use std::fs;
+
+use ic_rust::{aco::{logger::file::{read_event_log, EventKind, FileLogger}, stop::StopReason}, aco_protein_folding_2dhp, export::RunRecord, protein::parse_protein_sequence, ACOConfig, AcoEngine, CancellationToken, RunReport};
+
+fn config(max_iter: u16) -> ACOConfig {
+    serde_json::from_str(&format!(r#"{{
+        "ant_count": 5, "max_iter": {}, "no_impr_max": 10, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+        "neutral_mutation_rate": 0.5, "seed": 1
+    }}"#, max_iter)).unwrap()
+}
+
+/* Tudo que é feito com o relatório de uma execução sem nenhuma iteração completa */
+fn check_empty_report(report: &RunReport) {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+
+    assert!(report.iterations.is_empty());
+    assert!(!report.best.is_finite());
+    assert_eq!(report.best_iteration, None);
+    assert!(!report.best_conformation.is_fully_grown());
+    assert_eq!(report.best_conformation.to_string(), "");
+
+    let record = RunRecord::new(&protein, report.best_conformation.config(), report);
+    assert_eq!(record.conformation, "");
+    assert!(record.best_trace.is_empty());
+
+    let snapshot = report.pheromones.snapshot(&protein, report.best.is_finite().then_some(&report.best_conformation));
+    assert_eq!(snapshot.best_conformation, None);
+}
+
+#[test]
+fn run_without_iterations_does_not_panic() {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+    let path = std::env::temp_dir().join(format!("ic_rust_empty_run_{}.jsonl", std::process::id()));
+
+    let logger = FileLogger::create(&path).unwrap().with_pheromones(true);
+    let report = aco_protein_folding_2dhp(&protein, config(0), logger);
+    check_empty_report(&report);
+    assert_eq!(report.stop_reason, StopReason::MaxIterations);
+
+    /* O `RunEnd` não grava a melhor conformação, nem no snapshot dos feromônios */
+    let records = read_event_log(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let end = records.last().unwrap();
+    assert_eq!(end.kind, EventKind::RunEnd);
+    assert_eq!(end.conformation, None);
+    assert_eq!(end.pheromones.as_ref().unwrap().best_conformation, None);
+}
+
+#[test]
+fn cancel_before_first_iteration_does_not_panic() {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let mut engine = AcoEngine::new(&protein, config(10)).with_cancellation(token);
+    assert!(engine.step(&mut ()).is_none());
+    assert!(engine.snapshot().is_none());
+
+    let report = engine.run(&mut ());
+    check_empty_report(&report);
+    assert_eq!(report.stop_reason, StopReason::Cancelled);
+}
+
+/* Sem formigas a melhor conformação nunca é construída, e as regras que depositam nela não podem usá-la */
+#[test]
+fn run_without_ants_does_not_panic() {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+
+    for rule in ["ant_system", "elitist", "rank_based", "max_min", "ant_colony_system"] {
+        let config: ACOConfig = serde_json::from_str(&format!(r#"{{
+            "ant_count": 0, "max_iter": 3, "no_impr_max": 10, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+            "neutral_mutation_rate": 0.5, "seed": 1, "pheromone_update": "{}"
+        }}"#, rule)).unwrap();
+
+        let report = aco_protein_folding_2dhp(&protein, config, ());
+        assert!(!report.best.is_finite(), "{}", rule);
+        assert!(!report.best_conformation.is_fully_grown());
+    }
+}
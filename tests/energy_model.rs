@@ -0,0 +1,12 @@
+use ic_rust::{protein::EnergyError, AminoAcid, EnergyModel};
+
+#[test]
+fn matrix_row_count_must_match_the_alphabet() {
+    let extra = EnergyModel::new("hp", "HP", &[vec![-1.0, 0.0], vec![0.0, 0.0], vec![0.0, 0.0]]);
+    assert!(matches!(extra, Err(EnergyError::ExtraRows { expected: 2, found: 3 })));
+
+    let missing = EnergyModel::new("hp", "HP", &[vec![-1.0, 0.0]]);
+    assert!(matches!(missing, Err(EnergyError::MissingRow(aa)) if aa == AminoAcid::from_char('P').unwrap()));
+
+    assert!(EnergyModel::new("hp", "HP", &[vec![-1.0, 0.0], vec![0.0, 0.0]]).is_ok());
+}
@@ -0,0 +1,87 @@
+use std::{fs, path::PathBuf};
+
+use ic_rust::{aco::stop::StopReason, aco_protein_folding_2dhp, export::{ExportError, ExportFormat, RunRecord, SCHEMA_VERSION}, protein::parse_protein_sequence, ACOConfig};
+
+fn record() -> RunRecord {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+    let config: ACOConfig = serde_json::from_str(r#"{
+        "ant_count": 5, "max_iter": 3, "no_impr_max": 5, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+        "neutral_mutation_rate": 0.5, "seed": 1
+    }"#).unwrap();
+
+    RunRecord::new(&protein, config, &aco_protein_folding_2dhp(&protein, config, ()))
+}
+
+fn path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ic_rust_export_{}_{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn records_round_trip() {
+    let record = record();
+
+    for (format, name) in [(ExportFormat::JsonLines, "round_trip.jsonl"), (ExportFormat::Csv, "round_trip.csv")] {
+        let path = path(name);
+        format.append(&path, &record).unwrap();
+        format.append(&path, &record).unwrap();
+
+        let read = format.read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].conformation, record.conformation);
+        assert_eq!(read[1].best_trace, record.best_trace);
+        assert_eq!(read[1].config, record.config);
+    }
+}
+
+#[test]
+fn other_schema_versions_are_rejected() {
+    let old = RunRecord { schema_version: SCHEMA_VERSION + 1, ..record() };
+
+    /* Linhas do arquivo: no CSV, o cabeçalho é a linha 1 */
+    for (format, name, line) in [(ExportFormat::JsonLines, "version.jsonl", 2), (ExportFormat::Csv, "version.csv", 3)] {
+        let path = path(name);
+        format.append(&path, &record()).unwrap();
+        format.append(&path, &old).unwrap();
+
+        let error = format.read(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(error, ExportError::SchemaVersion { line: l, found, expected } if l == line && found == SCHEMA_VERSION + 1 && expected == SCHEMA_VERSION), "{:?}", error);
+    }
+}
+
+/* Acrescentar num CSV de outra versão deixaria as colunas novas sob o cabeçalho antigo */
+#[test]
+fn csv_with_other_columns_is_not_appended() {
+    let path = path("stale_header.csv");
+    fs::write(&path, "schema_version,sequence,fitness\n1,HPHP,2\n").unwrap();
+
+    let error = ExportFormat::Csv.append(&path, &record()).unwrap_err();
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(matches!(error, ExportError::CsvHeader), "{:?}", error);
+    assert_eq!(content, "schema_version,sequence,fitness\n1,HPHP,2\n");
+}
+
+/* Registros gravados antes dos campos mais novos ainda podem ser lidos */
+#[test]
+fn newer_fields_have_defaults() {
+    let mut json = serde_json::to_value(record()).unwrap();
+    for field in ["energy_model", "relative_error", "evaluations", "stop_reason", "construction_stats"] {
+        json.as_object_mut().unwrap().remove(field);
+    }
+
+    let path = path("defaults.jsonl");
+    fs::write(&path, format!("{}\n", json)).unwrap();
+    let read = ExportFormat::JsonLines.read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read[0].energy_model, "hp");
+    assert_eq!(read[0].evaluations, 0);
+    assert_eq!(read[0].stop_reason, StopReason::MaxIterations);
+}
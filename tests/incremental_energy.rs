@@ -0,0 +1,84 @@
+mod common;
+
+use rand::{seq::IndexedRandom, Rng};
+
+use common::{config, full_eval, random_conformation, rebuilt_eval, rng, LATTICES};
+use ic_rust::{protein::{parse_protein_sequence, parse_protein_sequence_with}, Direction, EnergyModel, Protein};
+
+const MOVES: usize = 300;
+
+fn proteins() -> Vec<Protein> {
+    vec![
+        parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap(),
+        parse_protein_sequence("HHPPHPPHPPHPPHPPHPPHPPHH").unwrap(),
+        /* Modelo com interações repulsivas, para a variação também poder ser negativa */
+        parse_protein_sequence_with("HPNXHHNPXNHHPNXPHNHN", &EnergyModel::hpnx()).unwrap()
+    ]
+}
+
+/* A variação incremental dos giros e dos pull moves, somada a cada movimento, sempre bate com a
+   energia calculada do zero */
+#[test]
+fn pivot_delta_matches_full_eval() {
+    for lattice in LATTICES {
+        for (seed, protein) in proteins().iter().enumerate() {
+            let mut rng = rng(seed as u64);
+            let mut conformation = random_conformation(protein, config(lattice), &mut rng);
+            let directions = conformation.lattice().directions();
+            let mut applied = 0;
+
+            for _ in 0..MOVES {
+                let i = rng.random_range(0..conformation.directions().len());
+                let d: Direction = *directions.choose(&mut rng).unwrap();
+
+                if let Some(delta) = conformation.pivot_delta(i, d) {
+                    let before = conformation.eval();
+                    conformation.apply_pivot(i, d, delta);
+                    applied += 1;
+
+                    assert!(conformation.is_valid(), "{:?}", lattice);
+                    assert_eq!(conformation.eval(), full_eval(protein, &conformation), "{:?}, giro {} para {:?} a partir de {}", lattice, i, d, before);
+                    assert_eq!(Some(conformation.eval()), rebuilt_eval(protein, &conformation), "{:?}", lattice);
+                }
+            }
+
+            assert!(applied > MOVES / 10, "{:?}: só {} giros válidos", lattice, applied);
+        }
+    }
+}
+
+#[test]
+fn pull_delta_matches_full_eval() {
+    for lattice in LATTICES {
+        for (seed, protein) in proteins().iter().enumerate() {
+            let mut rng = rng(seed as u64);
+            let mut conformation = random_conformation(protein, config(lattice), &mut rng);
+            let mut applied = 0;
+
+            for _ in 0..MOVES {
+                let i = rng.random_range(0..protein.len());
+                let Some(pull) = conformation.pull_moves(i).choose(&mut rng).cloned() else {
+                    continue;
+                };
+
+                let before = conformation.eval();
+                let (delta, undo) = conformation.apply_pull(&pull);
+                applied += 1;
+                let scale = protein.model().scale();
+
+                assert_eq!(conformation.eval(), full_eval(protein, &conformation), "{:?}, pull move {:?}", lattice, pull);
+                assert_eq!(conformation.eval(), before + delta as f64 / scale);
+                assert_eq!(Some(conformation.eval()), rebuilt_eval(protein, &conformation), "{:?}", lattice);
+
+                /* Desfazer volta à energia anterior; às vezes mantém o movimento para a cadeia mudar */
+                if rng.random_bool(0.3) {
+                    conformation.undo_pull(undo);
+                    assert_eq!(conformation.eval(), before);
+                    assert_eq!(conformation.eval(), full_eval(protein, &conformation));
+                }
+            }
+
+            assert!(applied > MOVES / 10, "{:?}: só {} pull moves", lattice, applied);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use ic_rust::{aco_protein_folding_2dhp, protein::parse_protein_sequence, ACOConfig, Direction};
+
+fn config() -> ACOConfig {
+    serde_json::from_str(r#"{
+        "ant_count": 5, "max_iter": 5, "no_impr_max": 5, "evaporation": 0.5, "alpha": 1.0, "beta": 2.0,
+        "neutral_mutation_rate": 0.5, "seed": 3, "pheromone_update": "max_min"
+    }"#).unwrap()
+}
+
+/* Sem contatos possíveis a melhor solução tem fitness 0 e τmax também; as trilhas não podem ir a 0 */
+#[test]
+fn zero_fitness_best_keeps_the_trails() {
+    let protein = parse_protein_sequence("PPPPPPPPPPPP").unwrap();
+    let report = aco_protein_folding_2dhp(&protein, config(), ());
+
+    assert_eq!(report.best.fitness(), 0.0);
+    for i in 0..protein.len() - 2 {
+        for d in 0..3 {
+            assert!(report.pheromones.get_weight(i, Direction::from_index(d), 1.0) > 0.0, "aminoácido {}, direção {}", i, d);
+        }
+    }
+    assert!((report.pheromones.entropy() - 1.0).abs() < 1e-9);
+}
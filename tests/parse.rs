@@ -0,0 +1,60 @@
+use ic_rust::{protein::{parse_benchmarks, parse_protein_sequence, protein_to_string, ParseError, ParseErrorKind}, Energy};
+
+fn sequence_error(s: &str) -> ParseError {
+    parse_protein_sequence(s).unwrap_err()
+}
+
+fn benchmarks_error(s: &str) -> ParseError {
+    parse_benchmarks(s).unwrap_err()
+}
+
+fn error(line: usize, column: usize, kind: ParseErrorKind) -> ParseError {
+    ParseError { line, column, kind }
+}
+
+#[test]
+fn sequence_errors_point_to_the_character() {
+    assert_eq!(sequence_error("HPX"), error(1, 3, ParseErrorKind::InvalidResidue('X')));
+    assert_eq!(sequence_error("2HP"), error(1, 1, ParseErrorKind::CountWithoutResidue));
+    assert_eq!(sequence_error("H0PH"), error(1, 2, ParseErrorKind::InvalidCount("0".to_string())));
+    assert_eq!(sequence_error("HP(HP"), error(1, 3, ParseErrorKind::UnclosedParen));
+    assert_eq!(sequence_error("HPH)"), error(1, 4, ParseErrorKind::UnexpectedCloseParen));
+    assert_eq!(sequence_error("HP"), error(1, 1, ParseErrorKind::TooShort(2)));
+    assert_eq!(sequence_error(""), error(1, 1, ParseErrorKind::EmptySequence));
+}
+
+#[test]
+fn huge_counts_are_errors() {
+    assert_eq!(sequence_error("H4000000000"), error(1, 2, ParseErrorKind::CountTooLarge("4000000000".to_string())));
+    assert_eq!(sequence_error("PH65536"), error(1, 3, ParseErrorKind::CountTooLarge("65536".to_string())));
+    assert_eq!(sequence_error("P(H60000)2"), error(1, 2, ParseErrorKind::TooLong));
+
+    assert_eq!(parse_protein_sequence("H65535").unwrap().len(), 65535);
+}
+
+#[test]
+fn benchmark_errors_point_to_the_line_and_column() {
+    assert_eq!(benchmarks_error("9:HPHPPHHPHPPHPHHPPHPH\n4:  HPHX"), error(2, 8, ParseErrorKind::InvalidResidue('X')));
+    assert_eq!(benchmarks_error("x:HPHP"), error(1, 1, ParseErrorKind::InvalidEnergy("x".to_string())));
+    assert_eq!(benchmarks_error(">seq1 E=abc\nHPHP"), error(1, 9, ParseErrorKind::InvalidEnergy("abc".to_string())));
+    assert_eq!(benchmarks_error(">seq1 E=-3\nHPHP\n  PHX"), error(3, 5, ParseErrorKind::InvalidResidue('X')));
+    assert_eq!(benchmarks_error("HPHP\nH99999"), error(2, 2, ParseErrorKind::CountTooLarge("99999".to_string())));
+
+    /* Registro sem sequência: o erro fica na linha seguinte ao cabeçalho */
+    assert_eq!(benchmarks_error(">seq1\n\n3:HPHP"), error(2, 1, ParseErrorKind::EmptySequence));
+}
+
+#[test]
+fn legacy_line_ends_the_previous_record() {
+    let benchmarks = parse_benchmarks(">seq1 E=-3\nHPHP\nPHPH\n2:HHPP\nPPHH\nHHHH\n1:PHP").unwrap();
+    let sequences: Vec<String> = benchmarks.iter().map(|b| protein_to_string(b.protein.residues())).collect();
+
+    assert_eq!(sequences, ["HPHPPHPH", "HHPP", "PPHHHHHH", "PHP"]);
+    assert_eq!(benchmarks[0].name.as_deref(), Some("seq1"));
+    assert_eq!(benchmarks[0].optimum, Some(Energy::new(-3.0)));
+    assert_eq!(benchmarks[1].optimum, Some(Energy::from_contacts(2)));
+    assert_eq!(benchmarks[2].optimum, None);
+
+    /* Com a entrada anterior encerrada, um erro na linha antiga aponta para ela */
+    assert_eq!(benchmarks_error(">seq1\nHPHP\n2:HHX"), error(3, 5, ParseErrorKind::InvalidResidue('X')));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::collections::HashSet;
+
+use rand::seq::IndexedRandom;
+
+use common::{config, random_conformation, rebuilt_eval, rng, LATTICES};
+use ic_rust::{conformation::local_search::Neighbourhood, protein::parse_protein_sequence, ACOConfig, Conformation};
+
+/* Verifica do zero, sem `is_valid`: aminoácidos em posições distintas e vizinhos na rede */
+fn assert_self_avoiding(conformation: &Conformation) {
+    let positions = conformation.coordinates();
+    let lattice = conformation.lattice();
+
+    assert_eq!(positions.iter().collect::<HashSet<_>>().len(), positions.len(), "sobreposição em {:?}", positions);
+    for pair in positions.windows(2) {
+        assert!(lattice.are_adjacent(pair[0], pair[1]), "{:?} e {:?} não são vizinhos", pair[0], pair[1]);
+    }
+    assert!(conformation.is_valid());
+}
+
+/* Todo pull move gerado, de todo aminoácido, mantém a cadeia sem sobreposição, com as direções
+   relativas de acordo com as coordenadas, e desfazê-lo volta às coordenadas anteriores */
+#[test]
+fn every_pull_move_keeps_the_chain_self_avoiding() {
+    let protein = parse_protein_sequence("HPHPPHHPHPPHPHHPPHPH").unwrap();
+
+    for lattice in LATTICES {
+        let mut rng = rng(1);
+        let mut conformation = random_conformation(&protein, config(lattice), &mut rng);
+        let mut checked = 0;
+
+        for _ in 0..10 {
+            for i in 0..protein.len() {
+                for pull in conformation.pull_moves(i) {
+                    let before = conformation.coordinates().to_vec();
+
+                    let (_, undo) = conformation.apply_pull(&pull);
+                    assert_self_avoiding(&conformation);
+                    assert!(rebuilt_eval(&protein, &conformation).is_some(), "{:?}, pull move {:?}", lattice, pull);
+                    checked += 1;
+
+                    conformation.undo_pull(undo);
+                    assert_eq!(conformation.coordinates(), before);
+                }
+            }
+
+            /* Passa para outra conformação por um pull move qualquer */
+            let moves: Vec<_> = (0..protein.len()).flat_map(|i| conformation.pull_moves(i)).collect();
+            if let Some(pull) = moves.choose(&mut rng) {
+                conformation.apply_pull(pull);
+            }
+        }
+
+        assert!(checked > 0, "{:?}: nenhum pull move", lattice);
+    }
+}
+
+#[test]
+fn pull_move_local_search_keeps_the_chain_self_avoiding() {
+    let protein = parse_protein_sequence("HHPPHPPHPPHPPHPPHPPHPPHH").unwrap();
+
+    for lattice in LATTICES {
+        for neighbourhood in [Neighbourhood::PullMove, Neighbourhood::Hybrid] {
+            let mut rng = rng(2);
+            let config = ACOConfig { neighbourhood, ..config(lattice) };
+            let mut conformation = random_conformation(&protein, config, &mut rng);
+
+            for _ in 0..10 {
+                conformation.local_search(&mut rng);
+                assert_self_avoiding(&conformation);
+            }
+        }
+    }
+}